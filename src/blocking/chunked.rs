@@ -0,0 +1,134 @@
+//! Iterating a PinList a bounded number of nodes at a time
+
+use core::iter::Take;
+
+use mutex::ScopedRawMutex;
+
+use super::list::{Iter, PinList};
+
+/// A position to resume chunked iteration of a [`PinList`] from
+///
+/// See [`PinList::with_iter_chunk()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChunkToken {
+    /// The generation of the list when the token was created, or `None`
+    /// for a token at the front of the list
+    generation: Option<u32>,
+    /// The number of nodes already visited
+    pos: usize,
+}
+
+/// Nodes were attached, detached, or reordered since a [`ChunkToken`] was
+/// created
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ListModified;
+
+// ---- impl ChunkToken ----
+
+impl ChunkToken {
+    /// A token that starts iteration at the front of the list
+    pub const fn new() -> Self {
+        Self {
+            generation: None,
+            pos: 0,
+        }
+    }
+
+    /// The number of nodes visited before this token was created
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Call the given closure with an [`Iter`] over at most `n` nodes,
+    /// starting at the position of `token`.
+    ///
+    /// Returns the result of `f()`, and a token to pass to the next call to
+    /// continue where this one stopped, or `None` if this chunk reached the
+    /// end of the list.
+    ///
+    /// If any node was attached, detached, or reordered since `token` was
+    /// created, nothing is visited and [`ListModified`] is returned; start
+    /// again from [`ChunkToken::new()`]. Calls to [`PinList::with_cursor()`]
+    /// always count as a modification. Updating items in place does not.
+    ///
+    /// The blocking mutex is locked only for the duration of one chunk, so
+    /// other users of the list can run between chunks. Resuming walks past
+    /// the nodes already visited, so each chunk takes time proportional to
+    /// its position in the list, but no item is accessed while doing so.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `n` is zero, as iteration could then never make progress.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{ChunkToken, ListModified, Node, PinList};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 10));
+    /// let node_b = pin!(Node::new_for(&LIST, 20));
+    /// let node_c = pin!(Node::new_for(&LIST, 30));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let _hdl_c = node_c.attach();
+    ///
+    /// let mut sum = 0;
+    /// let mut token = Some(ChunkToken::new());
+    /// while let Some(tok) = token {
+    ///     let (chunk, next) = LIST.with_iter_chunk(tok, 2, |n| n.sum::<u64>()).unwrap();
+    ///     sum += chunk;
+    ///     token = next;
+    /// }
+    /// assert_eq!(60, sum);
+    ///
+    /// // Modifying the list between chunks invalidates the token
+    /// let (_, next) = LIST.with_iter_chunk(ChunkToken::new(), 2, |_| ()).unwrap();
+    /// let node_d = pin!(Node::new_for(&LIST, 40));
+    /// let _hdl_d = node_d.attach();
+    /// let res = LIST.with_iter_chunk(next.unwrap(), 2, |_| ());
+    /// assert_eq!(Err(ListModified), res);
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_iter_chunk<U, F>(
+        &self,
+        token: ChunkToken,
+        n: usize,
+        f: F,
+    ) -> Result<(U, Option<ChunkToken>), ListModified>
+    where
+        F: for<'a> FnOnce(Take<Iter<'a, T>>) -> U,
+    {
+        assert_ne!(n, 0, "`with_iter_chunk()` called with a chunk size of zero");
+        self.inner.with_lock(|inner| {
+            if token.generation.is_some_and(|g| g != inner.generation) {
+                return Err(ListModified);
+            }
+            let mut iter = inner.list.iter();
+            if token.pos > 0 {
+                iter.nth(token.pos - 1);
+            }
+            let remaining = inner.list.len().saturating_sub(token.pos);
+            let res = f(Iter { iter }.take(n));
+            let next = (remaining > n).then(|| ChunkToken {
+                generation: Some(inner.generation),
+                pos: token.pos + n,
+            });
+            Ok((res, next))
+        })
+    }
+}
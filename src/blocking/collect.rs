@@ -0,0 +1,81 @@
+//! Copying the items of a PinList out from under the lock
+
+use mutex::ScopedRawMutex;
+
+use super::list::PinList;
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T: Clone> PinList<R, T> {
+    /// Clone the items of up to the first `N` nodes into a [`heapless::Vec`].
+    ///
+    /// If more than `N` nodes are attached, the rest are not cloned. The
+    /// blocking mutex is locked once, for the duration of the copy, so the
+    /// items can then be processed without holding it.
+    ///
+    /// Requires the `heapless` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(all(feature = "_docs", feature = "heapless"))]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let node_b = pin!(Node::new_for(&LIST, 456));
+    /// let node_c = pin!(Node::new_for(&LIST, 789));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let _hdl_c = node_c.attach();
+    ///
+    /// let items = LIST.snapshot_heapless::<2>();
+    /// assert_eq!(&[123, 456], items.as_slice());
+    /// # }
+    /// # #[cfg(all(feature = "_docs", feature = "heapless"))]
+    /// # example()
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn snapshot_heapless<const N: usize>(&self) -> heapless::Vec<T, N> {
+        self.with_iter(|iter| iter.take(N).cloned().collect())
+    }
+
+    /// Clone the items of all nodes into a [`Vec`](alloc::vec::Vec).
+    ///
+    /// The blocking mutex is locked once, for the duration of the copy, so the
+    /// items can then be processed without holding it.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(all(feature = "_docs", feature = "alloc"))]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let node_b = pin!(Node::new_for(&LIST, 456));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// assert_eq!(vec![123, 456], LIST.to_vec());
+    /// # }
+    /// # #[cfg(all(feature = "_docs", feature = "alloc"))]
+    /// # example()
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> alloc::vec::Vec<T> {
+        self.with_iter(|iter| iter.cloned().collect())
+    }
+}
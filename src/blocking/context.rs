@@ -0,0 +1,118 @@
+//! A PinList carrying a shared context for the code visiting its nodes
+
+use core::ops::Deref;
+
+use mutex::{ConstInit, ScopedRawMutex};
+
+use super::list::{Iter, IterPinMut, PinList};
+
+/// A [`PinList`] together with a context `C` that is shared by all of its
+/// nodes
+///
+/// The context is set when the list is created, and is passed to the closures
+/// of [`ContextPinList::with_iter_ctx()`] and
+/// [`ContextPinList::with_iter_pin_mut_ctx()`] alongside the iterator. This is
+/// useful for state that every visited node needs, such as a handle to a bus,
+/// without capturing it in each closure.
+///
+/// A `ContextPinList` dereferences to its [`PinList`], so nodes are created
+/// and attached in the same way, and all other methods are available.
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{ContextPinList, Node};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// struct Bus {
+///     scale: u64,
+/// }
+///
+/// static LIST: ContextPinList<CsRm, Bus, u64> = ContextPinList::new(Bus { scale: 10 });
+///
+/// let node_a = pin!(Node::new_for(&LIST, 1));
+/// let node_b = pin!(Node::new_for(&LIST, 2));
+/// let _hdl_a = node_a.attach();
+/// let _hdl_b = node_b.attach();
+///
+/// let scaled = LIST.with_iter_ctx(|bus, n| n.map(|t| t * bus.scale).collect::<Vec<_>>());
+/// assert_eq!(&[10, 20], scaled.as_slice());
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+pub struct ContextPinList<R: ScopedRawMutex, C, T> {
+    list: PinList<R, T>,
+    ctx: C,
+}
+
+// ---- impl ContextPinList ----
+
+impl<R: ScopedRawMutex + ConstInit, C, T> ContextPinList<R, C, T> {
+    /// Create a new [`ContextPinList`] with the given context.
+    ///
+    /// Requires that the mutex implements the [`ConstInit`] trait.
+    pub const fn new(ctx: C) -> Self {
+        Self {
+            list: PinList::new(),
+            ctx,
+        }
+    }
+}
+
+impl<R: ScopedRawMutex, C, T> ContextPinList<R, C, T> {
+    /// Create a new [`ContextPinList`] with the given context and
+    /// [`ScopedRawMutex`].
+    ///
+    /// Mainly useful when your mutex cannot be created in const context.
+    pub const fn new_manual(r: R, ctx: C) -> Self {
+        Self {
+            list: PinList::new_manual(r),
+            ctx,
+        }
+    }
+
+    /// The list of this [`ContextPinList`]
+    pub fn list(&self) -> &PinList<R, T> {
+        &self.list
+    }
+
+    /// The context of this [`ContextPinList`]
+    pub fn context(&self) -> &C {
+        &self.ctx
+    }
+
+    /// Call the given closure with the context, and an [`Iter`] which
+    /// iterates over `&T`s
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_iter_ctx<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(&'a C, Iter<'a, T>) -> U,
+    {
+        self.list.with_iter(|iter| f(&self.ctx, iter))
+    }
+
+    /// Call the given closure with the context, and an [`IterPinMut`] which
+    /// iterates over `Pin<&mut T>`s
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_iter_pin_mut_ctx<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(&'a C, IterPinMut<'a, T>) -> U,
+    {
+        self.list.with_iter_pin_mut(|iter| f(&self.ctx, iter))
+    }
+}
+
+impl<R: ScopedRawMutex, C, T> Deref for ContextPinList<R, C, T> {
+    type Target = PinList<R, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
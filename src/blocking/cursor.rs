@@ -0,0 +1,238 @@
+//! A cursor for editing a PinList in place
+
+#[cfg(feature = "attach-location")]
+use core::panic::Location;
+use core::{
+    cmp,
+    pin::Pin,
+    ptr::{self, NonNull, addr_of},
+    sync::atomic::Ordering,
+};
+
+use mutex::ScopedRawMutex;
+
+use super::{
+    list::{PinList, current_raw},
+    node::{Node, NodeHandle, NodeHeader},
+};
+
+/// A cursor over the nodes of a [`PinList`], which can move in either
+/// direction and add or remove nodes at its position
+///
+/// The cursor always points at either a node, or the "null" position between
+/// the back and the front of the list.
+///
+/// Obtained by calling [`PinList::with_cursor()`].
+pub struct Cursor<'a, R: ScopedRawMutex, T> {
+    cursor: cordyceps::list::CursorMut<'a, NodeHeader<T>>,
+    next_index: &'a mut u32,
+    next_generation: &'a mut u64,
+    order: Option<fn(&T, &T) -> cmp::Ordering>,
+    list: &'a PinList<R, T>,
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Call the given closure with a [`Cursor`] pointing at the front of the list
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    /// Watermarks are checked once `f()` returns, rather than after each
+    /// node is added or removed, and the list is always considered modified.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 10));
+    /// let node_b = pin!(Node::new_for(&LIST, 30));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// // Insert a node in order, in a single critical section
+    /// let node_c = pin!(Node::new_for(&LIST, 20));
+    /// let _hdl_c = LIST.with_cursor(|mut cursor| {
+    ///     while cursor.current().is_some_and(|t| *t < 20) {
+    ///         cursor.move_next();
+    ///     }
+    ///     cursor.insert_before(node_c)
+    /// });
+    ///
+    /// let items = LIST.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[10, 20, 30], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_cursor<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(Cursor<'a, R, T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            let res = f(Cursor {
+                cursor: inner.list.cursor_front_mut(),
+                next_index: &mut inner.next_index,
+                next_generation: &mut inner.next_node_generation,
+                order: inner.order,
+                list: self,
+            });
+            inner.changed();
+            res
+        })
+    }
+}
+
+// ---- impl Cursor ----
+
+impl<'a, R: ScopedRawMutex, T> Cursor<'a, R, T> {
+    /// Move to the next node.
+    ///
+    /// If the cursor is at the null position, this moves to the front of the
+    /// list. If it is at the back of the list, this moves to the null position.
+    pub fn move_next(&mut self) {
+        self.cursor.move_next();
+    }
+
+    /// Move to the previous node.
+    ///
+    /// If the cursor is at the null position, this moves to the back of the
+    /// list. If it is at the front of the list, this moves to the null position.
+    pub fn move_prev(&mut self) {
+        self.cursor.move_prev();
+    }
+
+    /// The item of the current node, or `None` at the null position
+    pub fn current(&self) -> Option<&T> {
+        self.cursor.current().map(|hdr| &hdr.get_ref().t)
+    }
+
+    /// The item of the current node as a `Pin<&mut T>`, or `None` at the
+    /// null position
+    pub fn current_pin_mut(&mut self) -> Option<Pin<&mut T>> {
+        let hdr = current_raw(&mut self.cursor)?;
+        // SAFETY: We hold the lock of the list the node is linked into, and the
+        // item stays borrowed through `self` while the reference exists.
+        Some(unsafe { NodeHeader::item_pin_mut(hdr) })
+    }
+
+    /// Attach `node` before the current node.
+    ///
+    /// At the null position, the node is attached at the back of the list.
+    ///
+    /// If the node was not created for this list, or this is an ordered list
+    /// and the node does not belong at this position, it is NOT attached, and
+    /// is returned back. If the node is already attached, it is left where it
+    /// is. Otherwise this behaves like [`Node::attach()`].
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn insert_before<'list, 'node>(
+        &mut self,
+        node: Pin<&'node mut Node<'list, R, T>>,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, Pin<&'node mut Node<'list, R, T>>> {
+        if !node.is_for(self.list) {
+            return Err(node);
+        }
+        let order = self.order;
+        // SAFETY: The node is for this list, and we hold its lock
+        unsafe {
+            node.attach_locked(
+                self.next_index,
+                self.next_generation,
+                |hdr| {
+                    if !Self::fits(order, self.cursor.peek_prev(), self.cursor.current(), hdr) {
+                        return false;
+                    }
+                    self.cursor.insert_before(hdr);
+                    true
+                },
+                #[cfg(feature = "attach-location")]
+                Location::caller(),
+            )
+        }
+    }
+
+    /// Attach `node` after the current node.
+    ///
+    /// At the null position, the node is attached at the front of the list.
+    ///
+    /// If the node was not created for this list, or this is an ordered list
+    /// and the node does not belong at this position, it is NOT attached, and
+    /// is returned back. If the node is already attached, it is left where it
+    /// is. Otherwise this behaves like [`Node::attach()`].
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn insert_after<'list, 'node>(
+        &mut self,
+        node: Pin<&'node mut Node<'list, R, T>>,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, Pin<&'node mut Node<'list, R, T>>> {
+        if !node.is_for(self.list) {
+            return Err(node);
+        }
+        let order = self.order;
+        // SAFETY: The node is for this list, and we hold its lock
+        unsafe {
+            node.attach_locked(
+                self.next_index,
+                self.next_generation,
+                |hdr| {
+                    if !Self::fits(order, self.cursor.current(), self.cursor.peek_next(), hdr) {
+                        return false;
+                    }
+                    self.cursor.insert_after(hdr);
+                    true
+                },
+                #[cfg(feature = "attach-location")]
+                Location::caller(),
+            )
+        }
+    }
+
+    /// Would inserting `hdr` between `lo` and `hi` keep an ordered list sorted?
+    ///
+    /// Always true for lists that are not ordered.
+    fn fits(
+        order: Option<fn(&T, &T) -> cmp::Ordering>,
+        lo: Option<Pin<&NodeHeader<T>>>,
+        hi: Option<Pin<&NodeHeader<T>>>,
+        hdr: NonNull<NodeHeader<T>>,
+    ) -> bool {
+        let Some(cmp) = order else {
+            return true;
+        };
+        // SAFETY: The node is live, and is not yet visible to anyone else
+        let t = unsafe { &*addr_of!((*hdr.as_ptr()).t) };
+        lo.is_none_or(|lo| cmp(&lo.t, t).is_le()) && hi.is_none_or(|hi| cmp(t, &hi.t).is_le())
+    }
+
+    /// Detach the current node from the list, and move to the next node.
+    ///
+    /// The detached node stays detached until it is attached again by its
+    /// owner. Returns `false` if the cursor is at the null position.
+    pub fn remove_current(&mut self) -> bool {
+        match self.cursor.remove_current() {
+            Some(hdr) => {
+                // SAFETY: The node was linked into this list, so it is live, and
+                // we hold the lock of this list.
+                unsafe { hdr.as_ref() }
+                    .linked_to
+                    .store(ptr::null_mut(), Ordering::Release);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<R: ScopedRawMutex, T: Unpin> Cursor<'_, R, T> {
+    /// The item of the current node as a `&mut T`, or `None` at the null
+    /// position
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        self.current_pin_mut().map(Pin::into_inner)
+    }
+}
@@ -0,0 +1,130 @@
+//! Writing the nodes of a PinList to a formatter
+
+use core::{fmt, ptr, sync::atomic::Ordering};
+
+use mutex::ScopedRawMutex;
+
+use super::list::PinList;
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T: fmt::Display> PinList<R, T> {
+    /// Write the item of each node to `w` with [`fmt::Display`], separated
+    /// by `sep`.
+    ///
+    /// The blocking mutex is locked for the duration of the call.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, &str> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, "uart"));
+    /// let node_b = pin!(Node::new_for(&LIST, "spi"));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// let mut out = String::new();
+    /// LIST.with_write_all(&mut out, ", ").unwrap();
+    /// assert_eq!("uart, spi", out);
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_write_all<W: fmt::Write>(&self, w: &mut W, sep: &str) -> fmt::Result {
+        self.write_all_with(w, sep, |w, t| write!(w, "{t}"))
+    }
+}
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    fn write_all_with<W, F>(&self, w: &mut W, sep: &str, mut item: F) -> fmt::Result
+    where
+        W: fmt::Write,
+        F: FnMut(&mut W, &T) -> fmt::Result,
+    {
+        self.with_iter(|iter| {
+            for (i, t) in iter.enumerate() {
+                if i != 0 {
+                    w.write_str(sep)?;
+                }
+                item(w, t)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<R: ScopedRawMutex, T: fmt::Debug> PinList<R, T> {
+    /// Write the item of each node to `w` with [`fmt::Debug`], separated by
+    /// `sep`.
+    ///
+    /// The blocking mutex is locked for the duration of the call.
+    pub fn with_write_all_debug<W: fmt::Write>(&self, w: &mut W, sep: &str) -> fmt::Result {
+        self.write_all_with(w, sep, |w, t| write!(w, "{t:?}"))
+    }
+
+    /// Write the structure of the list to `w`, one line per node.
+    ///
+    /// The first line shows the head, tail, and length recorded by the list.
+    /// Each following line shows the position, address, and index of a node,
+    /// the raw pointers to its neighbors, and its item. Nodes that do not
+    /// record this list as the one they are linked into are marked with
+    /// `FOREIGN`.
+    ///
+    /// This follows the links as they are, so it is useful for inspecting a
+    /// list that has been corrupted by unsafe code elsewhere. At most as many
+    /// nodes as the list's recorded length are visited, even if the links form
+    /// a cycle. The blocking mutex is locked for the duration of the dump.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let node_b = pin!(Node::new_for(&LIST, 456));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// let mut out = String::new();
+    /// LIST.debug_dump(&mut out).unwrap();
+    /// assert_eq!(3, out.lines().count());
+    /// assert!(out.lines().nth(2).unwrap().ends_with("456"));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn debug_dump<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let this = self.as_erased();
+        self.inner.with_lock(|inner| {
+            writeln!(w, "{:?}", inner.list)?;
+            for (i, hdr) in inner.list.iter().enumerate() {
+                let foreign = !ptr::eq(hdr.linked_to.load(Ordering::Acquire), this);
+                writeln!(
+                    w,
+                    "[{i}] {:p} #{}{} {:?} {:?}",
+                    hdr,
+                    hdr.index,
+                    if foreign { " FOREIGN" } else { "" },
+                    hdr.links,
+                    hdr.t,
+                )?;
+            }
+            Ok(())
+        })
+    }
+}
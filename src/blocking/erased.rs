@@ -0,0 +1,165 @@
+//! Handles to attached nodes that do not name the mutex type of their list
+
+use core::{
+    marker::PhantomData,
+    pin::Pin,
+    ptr::{NonNull, addr_of, addr_of_mut},
+    sync::atomic::Ordering,
+};
+
+use mutex::ScopedRawMutex;
+
+use super::{
+    list::{PinList, PinListInner},
+    node::{NodeHandle, NodeHeader},
+};
+
+/// A [`NodeHandle`] with the mutex type of its list erased
+///
+/// This allows handles of nodes in lists with different mutex types to be
+/// stored together. The mutex is locked through a function pointer chosen
+/// when the handle is erased, so access costs one indirect call more than
+/// with a [`NodeHandle`].
+///
+/// Obtained by calling [`NodeHandle::erase()`].
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(all(feature = "_docs", feature = "chaos"))]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{ChaosRawMutex, ErasedNodeHandle, Node, PinList};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST_A: PinList<CsRm, u64> = PinList::new();
+/// static LIST_B: PinList<ChaosRawMutex<CsRm>, u64> = PinList::new();
+///
+/// let node_a = pin!(Node::new_for(&LIST_A, 1));
+/// let node_b = pin!(Node::new_for(&LIST_B, 2));
+/// let handles: [ErasedNodeHandle<'_, '_, u64>; 2] =
+///     [node_a.attach().erase(), node_b.attach().erase()];
+///
+/// let sum: u64 = handles.iter().map(|h| h.with_lock(|t| *t)).sum();
+/// assert_eq!(3, sum);
+/// # }
+/// # #[cfg(all(feature = "_docs", feature = "chaos"))]
+/// # example()
+/// ```
+pub struct ErasedNodeHandle<'list, 'node, T> {
+    hdr: NonNull<NodeHeader<T>>,
+    /// The `PinList<R, T>` the node was created for
+    list: NonNull<()>,
+    with_inner: WithInnerFn<T>,
+    _lt: PhantomData<(&'list (), &'node mut ())>,
+}
+
+/// The signature of [`with_inner_for()`]
+type WithInnerFn<T> =
+    unsafe fn(NonNull<NodeHeader<T>>, NonNull<()>, &mut dyn FnMut(&mut PinListInner<T>));
+
+/// Call `f` with the inner state of the list `hdr` is linked into locked, or
+/// of `list` if it is not linked.
+///
+/// # Safety
+///
+/// `hdr` must point to a live node created for `list`, which must be a live
+/// `PinList<R, T>`.
+unsafe fn with_inner_for<R: ScopedRawMutex, T>(
+    hdr: NonNull<NodeHeader<T>>,
+    list: NonNull<()>,
+    f: &mut dyn FnMut(&mut PinListInner<T>),
+) {
+    // SAFETY: The caller guarantees `hdr` is live, and the node was created for
+    // a list with the same mutex type as any it is linked into.
+    let res = unsafe { NodeHeader::with_linked_list::<R, _, _>(hdr, |inner| f(inner)) };
+    if let Err(f) = res {
+        // SAFETY: The caller guarantees `list` is a live `PinList<R, T>`
+        let list = unsafe { list.cast::<PinList<R, T>>().as_ref() };
+        list.inner.with_lock(f);
+    }
+}
+
+// ---- impl NodeHandle ----
+
+impl<'list, 'node, R: ScopedRawMutex, T> NodeHandle<'list, 'node, R, T> {
+    /// Erase the mutex type of this handle. See [`ErasedNodeHandle`].
+    pub fn erase(self) -> ErasedNodeHandle<'list, 'node, T> {
+        ErasedNodeHandle {
+            hdr: self.hdr(),
+            list: NonNull::from(self.list()).cast(),
+            with_inner: with_inner_for::<R, T>,
+            _lt: PhantomData,
+        }
+    }
+}
+
+// ---- impl ErasedNodeHandle ----
+
+impl<T> ErasedNodeHandle<'_, '_, T> {
+    /// Call `f` with the mutex of the list this node is linked into locked,
+    /// or the mutex of the list it was created for, if it is not linked.
+    fn with_inner<U, F: FnOnce(&mut PinListInner<T>) -> U>(&self, f: F) -> U {
+        let mut f = Some(f);
+        let mut out = None;
+        // SAFETY: The handle borrows the node for 'node, and the list for 'list,
+        // so both are live, and `with_inner` was chosen for the list's mutex type.
+        unsafe {
+            (self.with_inner)(self.hdr, self.list, &mut |inner| {
+                if let Some(f) = f.take() {
+                    out = Some(f(inner));
+                }
+            });
+        }
+        out.expect("the closure is always called")
+    }
+
+    /// Access the item immutably within a closure.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    pub fn with_lock<U, F: FnOnce(&T) -> U>(&self, f: F) -> U {
+        let hdr = self.hdr;
+        self.with_inner(|_inner| {
+            // SAFETY: We hold the lock, and we are providing a &T reference, preventing
+            // the item from being moved out
+            f(unsafe { &*addr_of!((*hdr.as_ptr()).t) })
+        })
+    }
+
+    /// Access the item via a pinned mut reference within a closure.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    pub fn with_lock_pin_mut<U, F: FnOnce(Pin<&mut T>) -> U>(&self, f: F) -> U {
+        let hdr = self.hdr;
+        self.with_inner(|_inner| {
+            // SAFETY: We hold the lock, and we are providing a Pin<&mut T> reference,
+            // preventing the item from being moved out
+            f(unsafe { Pin::new_unchecked(&mut *addr_of_mut!((*hdr.as_ptr()).t)) })
+        })
+    }
+
+    /// The index assigned to this node when it was attached. See
+    /// [`NodeHandle::index()`].
+    pub fn index(&self) -> u32 {
+        // SAFETY: We hold the lock, so the list is not accessing the header
+        self.with_inner(|_inner| unsafe { *addr_of!((*self.hdr.as_ptr()).index) })
+    }
+
+    /// Is the node still attached to a list? See [`NodeHandle::is_attached()`].
+    pub fn is_attached(&self) -> bool {
+        // SAFETY: The handle borrows the node, so it is live, and `linked_to` is
+        // only accessed atomically
+        let linked_to = unsafe { &*addr_of!((*self.hdr.as_ptr()).linked_to) };
+        !linked_to.load(Ordering::Acquire).is_null()
+    }
+}
+
+impl<T: Unpin> ErasedNodeHandle<'_, '_, T> {
+    /// Access the item via a mut reference within a closure.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    pub fn with_lock_mut<U, F: FnOnce(&mut T) -> U>(&self, f: F) -> U {
+        self.with_lock_pin_mut(|t| f(Pin::into_inner(t)))
+    }
+}
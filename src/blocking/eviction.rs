@@ -0,0 +1,211 @@
+//! Asking the owners of nodes to detach them
+
+use core::{
+    cell::UnsafeCell,
+    future::Future,
+    pin::Pin,
+    ptr::addr_of,
+    sync::atomic::Ordering,
+    task::{Context, Poll, Waker},
+};
+
+use mutex::ScopedRawMutex;
+
+use super::{id::NodeId, list::PinList, node::NodeHandle};
+
+/// A [`Future`] that completes once the list asks for a node to be detached
+///
+/// Obtained by calling [`NodeHandle::wait_detach_requested()`].
+#[must_use = "futures do nothing unless polled"]
+pub struct WaitDetachRequested<'a, 'list, 'node, R: ScopedRawMutex, T> {
+    hdl: &'a NodeHandle<'list, 'node, R, T>,
+}
+
+/// The waker of the owner waiting in [`NodeHandle::wait_detach_requested()`]
+///
+/// Only accessed with the lock of the list the node is linked into held, or
+/// of the list it was created for, if it is not linked.
+pub(crate) struct DetachWaker(UnsafeCell<Option<Waker>>);
+
+// ---- impl DetachWaker ----
+
+impl DetachWaker {
+    pub(crate) const fn new() -> Self {
+        Self(UnsafeCell::new(None))
+    }
+
+    /// Store `waker`, replacing any waker stored before.
+    ///
+    /// # Safety
+    ///
+    /// The lock of the list the node is linked into, or of the list it was
+    /// created for if it is not linked, must be held.
+    unsafe fn register(&self, waker: &Waker) {
+        // SAFETY: The caller holds the lock, so this is the only access
+        let slot = unsafe { &mut *self.0.get() };
+        match slot {
+            Some(w) if w.will_wake(waker) => {}
+            _ => *slot = Some(waker.clone()),
+        }
+    }
+
+    /// Wake the stored waker, if any.
+    ///
+    /// # Safety
+    ///
+    /// The lock of the list the node is linked into must be held.
+    unsafe fn wake(&self) {
+        // SAFETY: The caller holds the lock, so this is the only access
+        if let Some(w) = unsafe { &mut *self.0.get() }.take() {
+            w.wake();
+        }
+    }
+}
+
+// SAFETY: The waker is only accessed with the lock of a list held, see above
+unsafe impl Sync for DetachWaker {}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Ask the owner of the node `id` to detach it.
+    ///
+    /// The list cannot remove a node it does not own, but the owner can poll
+    /// [`NodeHandle::detach_requested()`], or await
+    /// [`NodeHandle::wait_detach_requested()`], and detach or drop the node
+    /// when it is set. Returns `false` if the node is not attached to this list.
+    ///
+    /// The blocking mutex is locked while the list is searched for the node,
+    /// and while a waiting owner is woken.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let hdl_a = node_a.attach();
+    /// assert!(!hdl_a.detach_requested());
+    ///
+    /// // Evict the largest node
+    /// let id = LIST.with_iter_ids(|n| n.max_by_key(|(_, t)| **t).map(|(id, _)| id));
+    /// assert!(LIST.request_detach(id.unwrap()));
+    ///
+    /// // The owner notices, and complies
+    /// assert!(hdl_a.detach_requested());
+    /// let _node_a = hdl_a.detach();
+    /// assert!(LIST.is_empty());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn request_detach(&self, id: NodeId) -> bool {
+        self.inner.with_lock(|inner| {
+            let Some(hdr) = inner.list.iter().find(|hdr| id == NodeId::of(hdr)) else {
+                return false;
+            };
+            hdr.detach_requested.store(true, Ordering::Release);
+            // SAFETY: We hold the lock of the list the node is linked into
+            unsafe { hdr.detach_waker.wake() };
+            true
+        })
+    }
+
+    /// Ask the owners of all nodes to detach them. See
+    /// [`PinList::request_detach()`].
+    ///
+    /// Returns the number of nodes. The blocking mutex is locked for the
+    /// duration of the call.
+    pub fn request_detach_all(&self) -> usize {
+        self.inner.with_lock(|inner| {
+            for hdr in inner.list.iter() {
+                hdr.detach_requested.store(true, Ordering::Release);
+                // SAFETY: We hold the lock of the list the node is linked into
+                unsafe { hdr.detach_waker.wake() };
+            }
+            inner.list.len()
+        })
+    }
+}
+
+// ---- impl NodeHandle ----
+
+impl<'list, 'node, R: ScopedRawMutex, T> NodeHandle<'list, 'node, R, T> {
+    /// Has the list asked for this node to be detached?
+    ///
+    /// See [`PinList::request_detach()`]. The request is cleared when the node
+    /// is attached again. This does not lock the list.
+    pub fn detach_requested(&self) -> bool {
+        // SAFETY: The handle borrows the node, so it is live, and the flag is
+        // only accessed atomically
+        let flag = unsafe { &(*self.hdr().as_ptr()).detach_requested };
+        flag.load(Ordering::Acquire)
+    }
+
+    /// Wait until the list asks for this node to be detached.
+    ///
+    /// Completes immediately if a request is already pending. Only the most
+    /// recently polled waiter of each node is woken by a request. See
+    /// [`PinList::request_detach()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::{
+    ///     future::Future,
+    ///     pin::pin,
+    ///     task::{Context, Poll, Waker},
+    /// };
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let hdl_a = node_a.attach();
+    ///
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let mut wait = pin!(hdl_a.wait_detach_requested());
+    /// assert!(wait.as_mut().poll(&mut cx).is_pending());
+    ///
+    /// assert_eq!(1, LIST.request_detach_all());
+    /// assert!(wait.as_mut().poll(&mut cx).is_ready());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn wait_detach_requested(&self) -> WaitDetachRequested<'_, 'list, 'node, R, T> {
+        WaitDetachRequested { hdl: self }
+    }
+}
+
+// ---- impl WaitDetachRequested ----
+
+impl<R: ScopedRawMutex, T> Future for WaitDetachRequested<'_, '_, '_, R, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let hdr = self.hdl.hdr();
+        self.hdl.with_inner(|_inner| {
+            // SAFETY: The handle borrows the node, so it is live, and we hold the
+            // lock, which requests are made with, so none can be missed.
+            unsafe {
+                if (*addr_of!((*hdr.as_ptr()).detach_requested)).load(Ordering::Acquire) {
+                    return Poll::Ready(());
+                }
+                (*addr_of!((*hdr.as_ptr()).detach_waker)).register(cx.waker());
+            }
+            Poll::Pending
+        })
+    }
+}
@@ -0,0 +1,127 @@
+//! Opaque identities of the nodes of a PinList
+
+use core::ptr::NonNull;
+
+use mutex::ScopedRawMutex;
+
+use super::{
+    list::PinList,
+    node::{NodeHandle, NodeHeader},
+};
+
+/// An opaque identity of an attached node
+///
+/// Combines the address of the node with a generation, which the list stamps
+/// on the node each time it is linked into it, whether by attaching or by
+/// moving from another list. A node that is detached and attached again, or
+/// a new node that reuses the memory of an old one, gets a different
+/// `NodeId`. This makes it possible to match the nodes visited by
+/// [`PinList::with_iter_ids()`] to handles held elsewhere, without comparing
+/// items.
+///
+/// Generations are counted per list, so ids are only unique among the nodes
+/// of one list, and should only be compared with ids from the same list.
+///
+/// Obtained by calling [`NodeHandle::id()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId {
+    addr: usize,
+    generation: u64,
+}
+
+/// An [`Iterator`] over `(NodeId, &T)` items of a [`PinList`]
+///
+/// Obtained by calling [`PinList::with_iter_ids()`].
+pub struct IterIds<'a, T> {
+    iter: cordyceps::list::Iter<'a, NodeHeader<T>>,
+}
+
+// ---- impl NodeId ----
+
+impl NodeId {
+    pub(crate) fn of<T>(hdr: &NodeHeader<T>) -> Self {
+        Self {
+            addr: NonNull::from(hdr).addr().get(),
+            generation: hdr.generation,
+        }
+    }
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Call the given closure with an [`IterIds`] which iterates over
+    /// `(NodeId, &T)`s
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 7));
+    /// let node_b = pin!(Node::new_for(&LIST, 7));
+    /// let _hdl_a = node_a.attach();
+    /// let hdl_b = node_b.attach();
+    ///
+    /// // Equal items, but only one is the node of `hdl_b`
+    /// let id_b = hdl_b.id();
+    /// let pos = LIST.with_iter_ids(|mut n| n.position(|(id, _)| id == id_b));
+    /// assert_eq!(Some(1), pos);
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_iter_ids<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(IterIds<'a, T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            f(IterIds {
+                iter: inner.list.iter(),
+            })
+        })
+    }
+}
+
+// ---- impl NodeHandle ----
+
+impl<R: ScopedRawMutex, T> NodeHandle<'_, '_, R, T> {
+    /// The identity of this node. See [`NodeId`].
+    ///
+    /// The mutex is locked briefly, as the generation changes if the node is
+    /// moved to another list, so this must not be called while iterating the
+    /// list.
+    pub fn id(&self) -> NodeId {
+        let hdr = self.hdr();
+        self.with_inner(|_inner| {
+            // SAFETY: We hold the lock of the list the node is linked into, which
+            // is required to change its generation
+            unsafe { NodeId::of(hdr.as_ref()) }
+        })
+    }
+}
+
+// ---- impl IterIds ----
+
+impl<'a, T> Iterator for IterIds<'a, T> {
+    type Item = (NodeId, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|hdr| (NodeId::of(hdr), &hdr.t))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterIds<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|hdr| (NodeId::of(hdr), &hdr.t))
+    }
+}
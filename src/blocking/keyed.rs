@@ -0,0 +1,125 @@
+//! Keyed dispatch over a PinList
+//!
+//! This is the shape needed by RPC-style servers, where endpoints or topics
+//! register pinned handlers under a route key, and incoming messages are
+//! dispatched to the handler with the matching key.
+
+use core::pin::Pin;
+
+use mutex::ScopedRawMutex;
+use pin_project::pin_project;
+
+use super::list::PinList;
+
+/// An item of a [`PinList`] that is identified by a key
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{Keyed, Node, PinList};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// type Route = Keyed<&'static str, fn(u32) -> u32>;
+/// static ROUTES: PinList<CsRm, Route> = PinList::new();
+///
+/// let double = pin!(Node::new_for(&ROUTES, Keyed::new("double", |x| x * 2)));
+/// let square = pin!(Node::new_for(&ROUTES, Keyed::new("square", |x| x * x)));
+/// let _hdl_d = double.attach();
+/// let _hdl_s = square.attach();
+///
+/// assert_eq!(Some(6), ROUTES.dispatch(&"double", 3));
+/// assert_eq!(Some(9), ROUTES.dispatch(&"square", 3));
+/// assert_eq!(None, ROUTES.dispatch(&"cube", 3));
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+#[pin_project]
+pub struct Keyed<K, H> {
+    key: K,
+    #[pin]
+    handler: H,
+}
+
+/// A handler that can be invoked by [`PinList::dispatch()`]
+///
+/// This is implemented for all `Unpin` closures taking a single argument.
+pub trait Handler<P> {
+    /// The value returned by the handler
+    type Output;
+
+    /// Handle the given payload
+    fn handle(self: Pin<&mut Self>, payload: P) -> Self::Output;
+}
+
+// ---- impl Keyed ----
+
+impl<K, H> Keyed<K, H> {
+    /// Create a new [`Keyed`] item.
+    pub const fn new(key: K, handler: H) -> Self {
+        Self { key, handler }
+    }
+
+    /// The key of this item
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// The handler of this item
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// The handler of this item, as a pinned mutable reference
+    pub fn handler_pin_mut(self: Pin<&mut Self>) -> Pin<&mut H> {
+        self.project().handler
+    }
+}
+
+// ---- impl Handler ----
+
+impl<P, U, F> Handler<P> for F
+where
+    F: FnMut(P) -> U + Unpin,
+{
+    type Output = U;
+
+    fn handle(self: Pin<&mut Self>, payload: P) -> Self::Output {
+        (self.get_mut())(payload)
+    }
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, K: PartialEq, H> PinList<R, Keyed<K, H>> {
+    /// Call the given closure with the handler of the first node matching `key`.
+    ///
+    /// Returns `None` if no node with a matching key is attached.
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_key<U, F>(&self, key: &K, f: F) -> Option<U>
+    where
+        F: FnOnce(Pin<&mut H>) -> U,
+    {
+        self.with_iter_pin_mut(|mut iter| {
+            let item = iter.find(|item| item.key == *key)?;
+            Some(f(item.handler_pin_mut()))
+        })
+    }
+
+    /// Invoke the handler of the first node matching `key` with `payload`.
+    ///
+    /// Returns `None` if no node with a matching key is attached.
+    ///
+    /// The blocking mutex is locked for the duration of the handler.
+    pub fn dispatch<P>(&self, key: &K, payload: P) -> Option<H::Output>
+    where
+        H: Handler<P>,
+    {
+        self.with_key(key, |handler| handler.handle(payload))
+    }
+}
@@ -1,11 +1,25 @@
 //! The list of a PinList
 
-use core::pin::Pin;
+#[cfg(feature = "attach-location")]
+use core::panic::Location;
+use core::{
+    cmp,
+    iter::Rev,
+    mem,
+    ops::ControlFlow,
+    pin::Pin,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-use cordyceps::List;
+use cordyceps::{List, list::CursorMut};
 use mutex::{BlockingMutex, ConstInit, ScopedRawMutex};
 
+use super::id::NodeId;
 use super::node::NodeHeader;
+#[cfg(feature = "metadata")]
+use super::node::NodeMeta;
+use super::watermark::Watermarks;
 
 /// An intrusive list of [`Node<T>`]s
 ///
@@ -18,14 +32,14 @@ pub struct PinList<R: ScopedRawMutex, T> {
 ///
 /// Obtained by calling [`PinList::with_iter()`].
 pub struct Iter<'a, T> {
-    iter: cordyceps::list::Iter<'a, NodeHeader<T>>,
+    pub(crate) iter: cordyceps::list::Iter<'a, NodeHeader<T>>,
 }
 
 /// An [`Iterator`] over `Pin<&mut T>` nodes of a [`PinList`]
 ///
 /// Obtained by calling [`PinList::with_iter_pin_mut()`].
 pub struct IterPinMut<'a, T> {
-    iter: cordyceps::list::IterMut<'a, NodeHeader<T>>,
+    pub(crate) iter: cordyceps::list::IterRaw<'a, NodeHeader<T>>,
 }
 
 /// An [`Iterator`] over `&mut T` nodes of a [`PinList`]
@@ -34,18 +48,254 @@ pub struct IterPinMut<'a, T> {
 ///
 /// Obtained by calling [`PinList::with_iter_mut()`].
 pub struct IterMut<'a, T: Unpin> {
-    iter: cordyceps::list::IterMut<'a, NodeHeader<T>>,
+    iter: IterPinMut<'a, T>,
+}
+
+/// An [`Iterator`] over `&T` nodes of a [`PinList`], along with the location
+/// each node was attached from
+///
+/// Requires the `attach-location` feature.
+///
+/// Obtained by calling [`PinList::with_iter_attached_at()`].
+#[cfg(feature = "attach-location")]
+pub struct IterAttachedAt<'a, T> {
+    iter: cordyceps::list::Iter<'a, NodeHeader<T>>,
+}
+
+/// An [`Iterator`] over `&T` nodes of a [`PinList`], along with the owner
+/// metadata of each node
+///
+/// Requires the `metadata` feature.
+///
+/// Obtained by calling [`PinList::with_iter_meta()`].
+#[cfg(feature = "metadata")]
+pub struct IterMeta<'a, T> {
+    iter: cordyceps::list::Iter<'a, NodeHeader<T>>,
+}
+
+/// An [`Iterator`] over `&T` nodes of a [`PinList`], along with the index
+/// assigned to each node when it was attached
+///
+/// Obtained by calling [`PinList::with_iter_enumerate()`].
+pub struct IterEnumerate<'a, T> {
+    iter: cordyceps::list::Iter<'a, NodeHeader<T>>,
+}
+
+/// An [`Iterator`] adapter which stops early once a flag is set
+///
+/// The flag is checked before each element is yielded, which allows another
+/// context (e.g. a watchdog or interrupt) to cut short a long iteration while
+/// the list is locked.
+///
+/// Obtained by calling `stop_on()` on one of the iterators of a [`PinList`].
+pub struct StopOn<'s, I> {
+    iter: I,
+    stop: &'s AtomicBool,
 }
 
 /// The inner core of [`PinList`] which is only accessible with the
 /// mutex locked.
 pub(crate) struct PinListInner<T> {
     pub(crate) list: List<NodeHeader<T>>,
+    pub(crate) watermarks: Option<Watermarks>,
+    /// The index to assign to the next node attached to this list
+    pub(crate) next_index: u32,
+    /// The generation to stamp on the next node linked into this list
+    pub(crate) next_node_generation: u64,
+    /// The order the nodes are kept in, for lists created with
+    /// [`PinList::new_ordered()`]
+    pub(crate) order: Option<fn(&T, &T) -> cmp::Ordering>,
+    /// The identity of the last node yielded by a round-robin iterator
+    pub(crate) rr_last: Option<NodeId>,
+    /// Incremented whenever nodes are added, removed, or reordered
+    pub(crate) generation: u32,
+    /// Set by [`PinList::close()`]
+    pub(crate) closed: bool,
+}
+
+/// Update a CRC-32 (IEEE) with the given bytes.
+#[cfg(feature = "integrity")]
+fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for b in bytes {
+        crc ^= u32::from(*b);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    crc
+}
+
+/// Stable merge sort of the nodes of `list`, by relinking them.
+///
+/// Recurses to a depth of `log2(list.len())`.
+pub(crate) fn merge_sort<T, F>(list: &mut List<NodeHeader<T>>, cmp: &mut F)
+where
+    F: FnMut(&T, &T) -> cmp::Ordering,
+{
+    let len = list.len();
+    if len < 2 {
+        return;
+    }
+    let mut right = list.split_off(len / 2);
+    merge_sort(list, cmp);
+    merge_sort(&mut right, cmp);
+
+    let mut left = mem::replace(list, List::new());
+    while let (Some(l), Some(r)) = (left.front(), right.front()) {
+        // Only take from the right if it is strictly less, to keep the sort stable
+        let take_right = cmp(&r.t, &l.t).is_lt();
+        let hdr = if take_right {
+            right.pop_front()
+        } else {
+            left.pop_front()
+        };
+        if let Some(hdr) = hdr {
+            list.push_back(hdr);
+        }
+    }
+    list.append(&mut left);
+    list.append(&mut right);
+}
+
+/// The node `cursor` points at, as the pointer the list holds to it.
+///
+/// `CursorMut` only hands out that pointer when unlinking the node, so it is
+/// unlinked and immediately relinked in the same place. Unlike
+/// `CursorMut::current_mut()`, this does not create a `&mut NodeHeader`, see
+/// [`NodeHeader::item_pin_mut()`].
+pub(crate) fn current_raw<T>(
+    cursor: &mut CursorMut<'_, NodeHeader<T>>,
+) -> Option<NonNull<NodeHeader<T>>> {
+    let hdr = cursor.remove_current()?;
+    cursor.insert_before(hdr);
+    cursor.move_prev();
+    Some(hdr)
+}
+
+/// Link `hdr` into the sorted `list`, after all nodes that are not greater.
+///
+/// # Safety
+///
+/// `hdr` must point to a live, unlinked node, whose item is not being mutated.
+pub(crate) unsafe fn insert_sorted_by<T, F>(
+    list: &mut List<NodeHeader<T>>,
+    hdr: NonNull<NodeHeader<T>>,
+    mut cmp: F,
+) where
+    F: FnMut(&T, &T) -> cmp::Ordering,
+{
+    // SAFETY: The caller guarantees the node is live and not being mutated
+    let t = unsafe { &*ptr::addr_of!((*hdr.as_ptr()).t) };
+    let mut cursor = list.cursor_front_mut();
+    while cursor.current().is_some_and(|cur| cmp(&cur.t, t).is_le()) {
+        cursor.move_next();
+    }
+    // If we reached the end of the list, this inserts at the back
+    cursor.insert_before(hdr);
+}
+
+// ---- impl PinListInner ----
+
+impl<T> PinListInner<T> {
+    pub(crate) const fn new(order: Option<fn(&T, &T) -> cmp::Ordering>) -> Self {
+        Self {
+            list: List::new(),
+            watermarks: None,
+            next_index: 0,
+            next_node_generation: 0,
+            order,
+            rr_last: None,
+            generation: 0,
+            closed: false,
+        }
+    }
+
+    /// Record that nodes were added, removed, or reordered.
+    pub(crate) fn changed(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        self.check_watermarks();
+    }
+
+    /// Move the nodes from position `at` onwards to the back of `other`,
+    /// which is the inner state of the list at `other_addr`.
+    ///
+    /// Returns the number of nodes moved, which is zero if `at` is past the
+    /// end of the list.
+    ///
+    /// Must only be called with the locks of both lists held.
+    fn move_tail_to(&mut self, at: usize, other: &mut Self, other_addr: *mut ()) -> usize {
+        let Some(mut tail) = self.list.try_split_off(at) else {
+            return 0;
+        };
+        let moved = tail.len();
+        claim_all(&mut tail, other_addr, &mut other.next_node_generation);
+        other.list.append(&mut tail);
+        other.restore_order();
+        self.changed();
+        other.changed();
+        moved
+    }
+}
+
+/// Record `owner` as the list that every node of `list` is linked into, and
+/// stamp each node with a new generation from `next_generation`, the counter
+/// of `owner`.
+///
+/// Must only be called with the lock of `owner`, and of every list these
+/// nodes were previously linked into, held.
+fn claim_all<T>(list: &mut List<NodeHeader<T>>, owner: *mut (), next_generation: &mut u64) {
+    for hdr in list.iter_raw() {
+        // SAFETY: The node is linked into `list`, so it is live, and we hold
+        // the locks of every list it is or was linked into.
+        unsafe {
+            (*hdr.as_ptr()).generation = *next_generation;
+            (*hdr.as_ptr()).linked_to.store(owner, Ordering::Release);
+        }
+        *next_generation = next_generation.wrapping_add(1);
+    }
 }
 
 // ---- impl PinList ----
 
 impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// The number of nodes attached to the list
+    ///
+    /// The list keeps count of its nodes, so the blocking mutex is only locked
+    /// briefly, regardless of the number of nodes.
+    pub fn len(&self) -> usize {
+        self.inner.with_lock(|inner| inner.list.len())
+    }
+
+    /// Are there no nodes attached to the list?
+    ///
+    /// The blocking mutex is locked briefly.
+    pub fn is_empty(&self) -> bool {
+        self.inner.with_lock(|inner| inner.list.is_empty())
+    }
+
+    /// Close the list, so that [`Node::try_attach()`] fails.
+    ///
+    /// Nodes that are already attached are not affected. This is useful during
+    /// shutdown, so that new nodes are not attached to a list that will no
+    /// longer be serviced. The list can be opened again with
+    /// [`PinList::reopen()`].
+    ///
+    /// [`Node::try_attach()`]: crate::blocking::Node::try_attach
+    pub fn close(&self) {
+        self.inner.with_lock(|inner| inner.closed = true);
+    }
+
+    /// Open a list that was closed with [`PinList::close()`].
+    pub fn reopen(&self) {
+        self.inner.with_lock(|inner| inner.closed = false);
+    }
+
+    /// Has the list been closed with [`PinList::close()`]?
+    pub fn is_closed(&self) -> bool {
+        self.inner.with_lock(|inner| inner.closed)
+    }
+
     /// Call the given closure with an [`Iter`] which iterates over `&T`s
     ///
     /// The blocking mutex is locked for the duration of the call to `f()`.
@@ -60,6 +310,101 @@ impl<R: ScopedRawMutex, T> PinList<R, T> {
         })
     }
 
+    /// Call the given closure with an [`Iter`] which iterates over `&T`s,
+    /// from the back of the list to the front
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_iter_rev<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(Rev<Iter<'a, T>>) -> U,
+    {
+        self.with_iter(|iter| f(iter.rev()))
+    }
+
+    /// Call the given closure with an [`IterAttachedAt`] which iterates over
+    /// `(&'static Location, &T)`s
+    ///
+    /// Useful for finding out who attached a node that is unexpectedly still
+    /// present in the list.
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    ///
+    /// Requires the `attach-location` feature.
+    #[cfg(feature = "attach-location")]
+    pub fn with_iter_attached_at<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(IterAttachedAt<'a, T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            f(IterAttachedAt {
+                iter: inner.list.iter(),
+            })
+        })
+    }
+
+    /// Call the given closure with an [`IterMeta`] which iterates over
+    /// `(&NodeMeta, &T)`s
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    ///
+    /// Requires the `metadata` feature.
+    #[cfg(feature = "metadata")]
+    pub fn with_iter_meta<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(IterMeta<'a, T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            f(IterMeta {
+                iter: inner.list.iter(),
+            })
+        })
+    }
+
+    /// Call the given closure with an [`IterEnumerate`] which iterates over
+    /// `(u32, &T)`s
+    ///
+    /// Unlike [`Iterator::enumerate()`], the index is not the position of the
+    /// node in the list, but the index assigned to the node when it was
+    /// attached, which is stable even if the list is reordered. See
+    /// [`NodeHandle::index()`](crate::blocking::NodeHandle::index).
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{AttachPosition, PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, &'static str> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, "a"));
+    /// let node_b = pin!(Node::new_for(&LIST, "b"));
+    /// let hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach_at(AttachPosition::Front);
+    ///
+    /// let items = LIST.with_iter_enumerate(|n| n.map(|(i, t)| (i, *t)).collect::<Vec<_>>());
+    /// assert_eq!(&[(1, "b"), (0, "a")], items.as_slice());
+    /// assert_eq!(0, hdl_a.index());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_iter_enumerate<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(IterEnumerate<'a, T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            f(IterEnumerate {
+                iter: inner.list.iter(),
+            })
+        })
+    }
+
     /// Call the given closure with an [`IterPinMut`] which iterates over `Pin<&mut T>`s
     ///
     /// The blocking mutex is locked for the duration of the call to `f()`.
@@ -72,10 +417,879 @@ impl<R: ScopedRawMutex, T> PinList<R, T> {
     {
         self.inner.with_lock(|inner| {
             f(IterPinMut {
-                iter: inner.list.iter_mut(),
+                iter: inner.list.iter_raw(),
             })
         })
     }
+
+    /// Call the given closure with an [`IterPinMut`] which iterates over
+    /// `Pin<&mut T>`s, from the back of the list to the front
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_iter_pin_mut_rev<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(Rev<IterPinMut<'a, T>>) -> U,
+    {
+        self.with_iter_pin_mut(|iter| f(iter.rev()))
+    }
+
+    /// Call `f` with the first node matching `pred`.
+    ///
+    /// Returns `None` if no node matches.
+    ///
+    /// The blocking mutex is locked for the duration of the search and the
+    /// call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, (u32, &'static str)> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, (1, "a")));
+    /// let node_b = pin!(Node::new_for(&LIST, (2, "b")));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// assert_eq!(Some("b"), LIST.with_find(|t| t.0 == 2, |t| t.1));
+    /// assert_eq!(Some(1), LIST.with_find_map(|t| (t.1 == "a").then_some(t.0)));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_find<U, P, F>(&self, mut pred: P, f: F) -> Option<U>
+    where
+        P: FnMut(&T) -> bool,
+        F: FnOnce(&T) -> U,
+    {
+        self.with_iter(|mut iter| iter.find(|t| pred(t)).map(f))
+    }
+
+    /// Call `f` with each node in order, returning the first `Some` result.
+    ///
+    /// Returns `None` if `f` returns `None` for every node.
+    ///
+    /// The blocking mutex is locked for the duration of the search.
+    pub fn with_find_map<U, F>(&self, f: F) -> Option<U>
+    where
+        F: FnMut(&T) -> Option<U>,
+    {
+        self.with_iter(|mut iter| iter.find_map(f))
+    }
+
+    /// Call `f` with the first node matching `pred`, as a `Pin<&mut T>`.
+    ///
+    /// Returns `None` if no node matches.
+    ///
+    /// The blocking mutex is locked for the duration of the search and the
+    /// call to `f()`.
+    pub fn with_find_pin_mut<U, P, F>(&self, mut pred: P, f: F) -> Option<U>
+    where
+        P: FnMut(&T) -> bool,
+        F: FnOnce(Pin<&mut T>) -> U,
+    {
+        self.with_iter_pin_mut(|mut iter| iter.find(|t| pred(t)).map(f))
+    }
+
+    /// Call `f` with each node in order as a `Pin<&mut T>`, returning the
+    /// first `Some` result.
+    ///
+    /// Returns `None` if `f` returns `None` for every node.
+    ///
+    /// The blocking mutex is locked for the duration of the search.
+    pub fn with_find_map_pin_mut<U, F>(&self, f: F) -> Option<U>
+    where
+        F: FnMut(Pin<&mut T>) -> Option<U>,
+    {
+        self.with_iter_pin_mut(|mut iter| iter.find_map(f))
+    }
+
+    /// Call `f` with the node that was assigned `index` when it was attached.
+    ///
+    /// Returns `None` if no attached node has that index. Indices are unique
+    /// among the nodes attached to a list, unless they wrap around, or nodes
+    /// are moved in from another list. See
+    /// [`NodeHandle::index()`](crate::blocking::NodeHandle::index).
+    ///
+    /// The blocking mutex is locked for the duration of the search and the
+    /// call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, &'static str> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, "a"));
+    /// let node_b = pin!(Node::new_for(&LIST, "b"));
+    /// let _hdl_a = node_a.attach();
+    /// let hdl_b = node_b.attach_front();
+    ///
+    /// // e.g. received from a log message or a shell command
+    /// let index = hdl_b.index();
+    /// assert_eq!(Some("b"), LIST.with_find_by_index(index, |t| *t));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_find_by_index<U, F>(&self, index: u32, f: F) -> Option<U>
+    where
+        F: FnOnce(&T) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            let hdr = inner.list.iter().find(|hdr| hdr.index == index)?;
+            Some(f(&hdr.t))
+        })
+    }
+
+    /// Call `f` with the node that was assigned `index` when it was attached,
+    /// as a `Pin<&mut T>`. See [`PinList::with_find_by_index()`].
+    ///
+    /// The blocking mutex is locked for the duration of the search and the
+    /// call to `f()`.
+    pub fn with_find_by_index_pin_mut<U, F>(&self, index: u32, f: F) -> Option<U>
+    where
+        F: FnOnce(Pin<&mut T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            let hdr = inner
+                .list
+                .iter_raw()
+                // SAFETY: We hold the lock, so the node is live
+                .find(|hdr| unsafe { hdr.as_ref() }.index == index)?;
+            // SAFETY: We hold the lock of the list the node is linked into
+            Some(f(unsafe { NodeHeader::item_pin_mut(hdr) }))
+        })
+    }
+
+    /// Call `f` with each node in order, stopping early if it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// Returns the `Break` value, or `Continue` if every node was visited.
+    ///
+    /// The blocking mutex is locked until all nodes are visited or `f` breaks.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::{ops::ControlFlow, pin::pin};
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // (handled event, handler name)
+    /// static LIST: PinList<CsRm, (u8, &'static str)> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, (1, "a")));
+    /// let node_b = pin!(Node::new_for(&LIST, (2, "b")));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// let handled_by = LIST.with_try_for_each(|t| {
+    ///     if t.0 == 1 {
+    ///         ControlFlow::Break(t.1)
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(ControlFlow::Break("a"), handled_by);
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_try_for_each<B, F>(&self, f: F) -> ControlFlow<B>
+    where
+        F: FnMut(&T) -> ControlFlow<B>,
+    {
+        self.with_iter(|mut iter| iter.try_for_each(f))
+    }
+
+    /// Call `f` with each node in order as a `Pin<&mut T>`, stopping early if
+    /// it returns [`ControlFlow::Break`].
+    ///
+    /// Returns the `Break` value, or `Continue` if every node was visited.
+    ///
+    /// The blocking mutex is locked until all nodes are visited or `f` breaks.
+    pub fn with_try_for_each_pin_mut<B, F>(&self, f: F) -> ControlFlow<B>
+    where
+        F: FnMut(Pin<&mut T>) -> ControlFlow<B>,
+    {
+        self.with_iter_pin_mut(|mut iter| iter.try_for_each(f))
+    }
+
+    /// Fold every node into an accumulator, starting from `init`.
+    ///
+    /// The blocking mutex is locked while all nodes are visited.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // Outstanding bytes of each transfer
+    /// static LIST: PinList<CsRm, usize> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 64));
+    /// let node_b = pin!(Node::new_for(&LIST, 128));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// assert_eq!(192, LIST.with_fold(0, |acc, t| acc + t));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        self.with_iter(|iter| iter.fold(init, f))
+    }
+
+    /// Does any node match `pred`?
+    ///
+    /// Returns `false` for an empty list. The blocking mutex is locked until a
+    /// matching node is found, or all nodes are visited.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // Is each client busy?
+    /// static LIST: PinList<CsRm, bool> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, false));
+    /// let node_b = pin!(Node::new_for(&LIST, true));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// assert!(LIST.with_any(|busy| *busy));
+    /// assert!(!LIST.with_all(|busy| *busy));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_any<P>(&self, pred: P) -> bool
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.with_iter(|mut iter| iter.any(pred))
+    }
+
+    /// Do all nodes match `pred`?
+    ///
+    /// Returns `true` for an empty list. The blocking mutex is locked until a
+    /// node that does not match is found, or all nodes are visited.
+    pub fn with_all<P>(&self, pred: P) -> bool
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.with_iter(|mut iter| iter.all(pred))
+    }
+
+    /// Call `f` with the node with the minimum value of `key`.
+    ///
+    /// If several nodes are equally minimum, the first is used. Returns `None`
+    /// if the list is empty.
+    ///
+    /// The blocking mutex is locked while all nodes are visited, and for the
+    /// call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // (deadline, waiter name)
+    /// static LIST: PinList<CsRm, (u64, &'static str)> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, (300, "a")));
+    /// let node_b = pin!(Node::new_for(&LIST, (100, "b")));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// // The earliest deadline
+    /// assert_eq!(Some(100), LIST.with_min_by_key(|t| t.0, |t| t.0));
+    /// // Who has the latest deadline?
+    /// assert_eq!(Some("a"), LIST.with_max_by_key(|t| t.0, |t| t.1));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_min_by_key<K, U, G, F>(&self, mut key: G, f: F) -> Option<U>
+    where
+        K: Ord,
+        G: FnMut(&T) -> K,
+        F: FnOnce(&T) -> U,
+    {
+        self.with_iter(|iter| iter.min_by_key(|t| key(t)).map(f))
+    }
+
+    /// Call `f` with the node with the maximum value of `key`.
+    ///
+    /// If several nodes are equally maximum, the last is used. Returns `None`
+    /// if the list is empty.
+    ///
+    /// The blocking mutex is locked while all nodes are visited, and for the
+    /// call to `f()`.
+    pub fn with_max_by_key<K, U, G, F>(&self, mut key: G, f: F) -> Option<U>
+    where
+        K: Ord,
+        G: FnMut(&T) -> K,
+        F: FnOnce(&T) -> U,
+    {
+        self.with_iter(|iter| iter.max_by_key(|t| key(t)).map(f))
+    }
+
+    /// Call `f` with the node with the minimum value of `key`, as a `Pin<&mut T>`.
+    ///
+    /// If several nodes are equally minimum, the first is used. Returns `None`
+    /// if the list is empty.
+    ///
+    /// The blocking mutex is locked while all nodes are visited, and for the
+    /// call to `f()`.
+    pub fn with_min_by_key_pin_mut<K, U, G, F>(&self, mut key: G, f: F) -> Option<U>
+    where
+        K: Ord,
+        G: FnMut(&T) -> K,
+        F: FnOnce(Pin<&mut T>) -> U,
+    {
+        self.with_iter_pin_mut(|iter| iter.min_by_key(|t| key(t)).map(f))
+    }
+
+    /// Call `f` with the node with the maximum value of `key`, as a `Pin<&mut T>`.
+    ///
+    /// If several nodes are equally maximum, the last is used. Returns `None`
+    /// if the list is empty.
+    ///
+    /// The blocking mutex is locked while all nodes are visited, and for the
+    /// call to `f()`.
+    pub fn with_max_by_key_pin_mut<K, U, G, F>(&self, mut key: G, f: F) -> Option<U>
+    where
+        K: Ord,
+        G: FnMut(&T) -> K,
+        F: FnOnce(Pin<&mut T>) -> U,
+    {
+        self.with_iter_pin_mut(|iter| iter.max_by_key(|t| key(t)).map(f))
+    }
+
+    /// Call `f` with the node at position `n`, counting from zero at the
+    /// front of the list, or `None` if there are not enough nodes.
+    ///
+    /// The blocking mutex is locked while walking to the node, and for the
+    /// call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let node_b = pin!(Node::new_for(&LIST, 456));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// assert_eq!(Some(456), LIST.with_nth(1, |t| t.copied()));
+    /// assert_eq!(None, LIST.with_nth(2, |t| t.copied()));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_nth<U, F>(&self, n: usize, f: F) -> U
+    where
+        F: FnOnce(Option<&T>) -> U,
+    {
+        self.with_iter(|mut iter| f(iter.nth(n)))
+    }
+
+    /// Call `f` with the node at position `n` as a `Pin<&mut T>`, counting
+    /// from zero at the front of the list, or `None` if there are not enough
+    /// nodes.
+    ///
+    /// The blocking mutex is locked while walking to the node, and for the
+    /// call to `f()`.
+    pub fn with_nth_pin_mut<U, F>(&self, n: usize, f: F) -> U
+    where
+        F: FnOnce(Option<Pin<&mut T>>) -> U,
+    {
+        self.with_iter_pin_mut(|mut iter| f(iter.nth(n)))
+    }
+
+    /// Sort the nodes of the list with the comparator `cmp`.
+    ///
+    /// This is a stable merge sort, which relinks the nodes in place without
+    /// allocating or moving any items. It takes `O(n log n)` time, and the
+    /// blocking mutex is locked for the duration of the sort.
+    ///
+    /// Nodes attached later are attached as usual, and are not kept sorted.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // (priority, name)
+    /// static LIST: PinList<CsRm, (u8, &'static str)> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, (2, "a")));
+    /// let node_b = pin!(Node::new_for(&LIST, (1, "b")));
+    /// let node_c = pin!(Node::new_for(&LIST, (2, "c")));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let _hdl_c = node_c.attach();
+    ///
+    /// LIST.with_sort_by_key(|t| t.0);
+    ///
+    /// let items = LIST.with_iter(|n| n.map(|t| t.1).collect::<Vec<_>>());
+    /// assert_eq!(&["b", "a", "c"], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_sort_by<F>(&self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> cmp::Ordering,
+    {
+        self.inner.with_lock(|inner| {
+            merge_sort(&mut inner.list, &mut cmp);
+            inner.changed();
+        })
+    }
+
+    /// Sort the nodes of the list by the key extracted by `key`.
+    ///
+    /// See [`PinList::with_sort_by()`] for details.
+    pub fn with_sort_by_key<K, F>(&self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.with_sort_by(|a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Apply a fallible update to every node, rolling back on the first error.
+    ///
+    /// `update` is called for each node in order. If it returns an error,
+    /// `undo` is called for each node that was already successfully updated,
+    /// in reverse order, and the error is returned. `update` is expected to
+    /// leave the node it failed on unchanged, and `undo` is not called for it.
+    ///
+    /// The blocking mutex is locked for the duration of the call, so no other
+    /// context can observe a partially applied update.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u8> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 10));
+    /// let node_b = pin!(Node::new_for(&LIST, 255));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// let res = LIST.with_try_update_all(
+    ///     |mut t| {
+    ///         *t = t.checked_add(1).ok_or("overflow")?;
+    ///         Ok(())
+    ///     },
+    ///     |mut t| *t -= 1,
+    /// );
+    /// assert_eq!(Err("overflow"), res);
+    ///
+    /// // Node A was rolled back
+    /// let items = LIST.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[10, 255], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_try_update_all<E, F, G>(&self, mut update: F, mut undo: G) -> Result<(), E>
+    where
+        F: FnMut(Pin<&mut T>) -> Result<(), E>,
+        G: FnMut(Pin<&mut T>),
+    {
+        self.inner.with_lock(|inner| {
+            let mut updated = 0;
+            let mut res = Ok(());
+            for hdr in inner.list.iter_raw() {
+                // SAFETY: We hold the lock of the list the node is linked into
+                if let Err(e) = update(unsafe { NodeHeader::item_pin_mut(hdr) }) {
+                    res = Err(e);
+                    break;
+                }
+                updated += 1;
+            }
+            if res.is_err() {
+                for hdr in inner.list.iter_raw().take(updated).rev() {
+                    // SAFETY: As above
+                    undo(unsafe { NodeHeader::item_pin_mut(hdr) });
+                }
+            }
+            res
+        })
+    }
+
+    /// Detach every node for which `f` returns `false`, visiting nodes in order.
+    ///
+    /// Detached nodes stay detached until they are attached again by their
+    /// owner, and dropping them will not need to lock the list. Returns the
+    /// number of nodes that were detached.
+    ///
+    /// The blocking mutex is locked for the duration of the call.
+    ///
+    /// If your type implements [`Unpin`], consider using [`PinList::with_retain()`]
+    /// if you would prefer `&mut T`s.
+    pub fn with_retain_pin_mut<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(Pin<&mut T>) -> bool,
+    {
+        self.inner.with_lock(|inner| {
+            let mut removed = 0;
+            let mut cursor = inner.list.cursor_front_mut();
+            while let Some(hdr) = current_raw(&mut cursor) {
+                // SAFETY: We hold the lock of the list the node is linked into
+                if f(unsafe { NodeHeader::item_pin_mut(hdr) }) {
+                    cursor.move_next();
+                    continue;
+                }
+                if let Some(hdr) = cursor.remove_current() {
+                    // SAFETY: The node was linked into this list, so it is live,
+                    // and we hold the lock of this list.
+                    unsafe { hdr.as_ref() }
+                        .linked_to
+                        .store(ptr::null_mut(), Ordering::Release);
+                    removed += 1;
+                }
+            }
+            if removed > 0 {
+                inner.changed();
+            }
+            removed
+        })
+    }
+
+    /// Call the given closure with each pair of adjacent nodes, as `Pin<&mut T>`s
+    ///
+    /// For a list of `[a, b, c]`, `f` is called with `(a, b)` and then `(b, c)`.
+    /// This allows updating nodes relative to their neighbor, e.g. for delta
+    /// encoding. `f` is not called if there are fewer than two nodes.
+    ///
+    /// The blocking mutex is locked for the duration of the call.
+    ///
+    /// If your type implements [`Unpin`], consider using
+    /// [`PinList::with_iter_pairs_mut()`] if you would prefer `&mut T`s.
+    pub fn with_iter_pairs_pin_mut<F>(&self, mut f: F)
+    where
+        F: FnMut(Pin<&mut T>, Pin<&mut T>),
+    {
+        self.inner.with_lock(|inner| {
+            let mut iter = inner.list.iter_raw();
+            let Some(mut prev) = iter.next() else {
+                return;
+            };
+            for cur in iter {
+                // SAFETY: We hold the lock of the list both nodes are linked into,
+                // and they are distinct nodes.
+                unsafe {
+                    f(
+                        NodeHeader::item_pin_mut(prev),
+                        NodeHeader::item_pin_mut(cur),
+                    )
+                };
+                prev = cur;
+            }
+        })
+    }
+}
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Exchange all nodes of this list with all nodes of `other`.
+    ///
+    /// Both mutexes are locked for the duration of the call. They are always
+    /// locked in the same order (by address), so concurrent calls with the
+    /// lists in either order will not deadlock. This must not be called while
+    /// holding the lock of either list.
+    ///
+    /// Both lists must be `'static`, as nodes will no longer live in the list
+    /// they were created for. Each moved node is updated to record the list it
+    /// now lives in, so this takes time proportional to the number of nodes.
+    /// Ordered lists are re-sorted after the exchange.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static ACTIVE: PinList<CsRm, u64> = PinList::new();
+    /// static STAGING: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&ACTIVE, 123));
+    /// let node_b = pin!(Node::new_for(&STAGING, 456));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// ACTIVE.swap_contents(&STAGING);
+    ///
+    /// let items = ACTIVE.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[456], items.as_slice());
+    /// let items = STAGING.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[123], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn swap_contents(&'static self, other: &'static Self) {
+        if core::ptr::eq(self, other) {
+            return;
+        }
+        self.with_lock_pair(other, |this, other_inner| {
+            mem::swap(&mut this.list, &mut other_inner.list);
+            claim_all(
+                &mut this.list,
+                self.as_erased(),
+                &mut this.next_node_generation,
+            );
+            claim_all(
+                &mut other_inner.list,
+                other.as_erased(),
+                &mut other_inner.next_node_generation,
+            );
+            this.restore_order();
+            other_inner.restore_order();
+            this.changed();
+            other_inner.changed();
+        })
+    }
+
+    /// Move the nodes from position `at` onwards to the back of `other`.
+    ///
+    /// Returns the number of nodes moved, which is zero if `at` is past the
+    /// end of the list. If `other` is ordered, it is re-sorted afterwards.
+    ///
+    /// Both mutexes are locked for the duration of the call, in the same way
+    /// as [`PinList::swap_contents()`], and as there, both lists must be
+    /// `'static`. Each moved node is updated to record the list it now lives
+    /// in, so it is unlinked from the correct list when dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static PENDING: PinList<CsRm, u64> = PinList::new();
+    /// static ACTIVE: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&PENDING, 1));
+    /// let node_b = pin!(Node::new_for(&PENDING, 2));
+    /// let node_c = pin!(Node::new_for(&PENDING, 3));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let _hdl_c = node_c.attach();
+    ///
+    /// // Promote everything from the first node above 1
+    /// assert_eq!(2, PENDING.split_off_by(|t| *t > 1, &ACTIVE));
+    ///
+    /// let items = ACTIVE.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[2, 3], items.as_slice());
+    /// assert_eq!(1, PENDING.len());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn split_off(&'static self, at: usize, other: &'static Self) -> usize {
+        if core::ptr::eq(self, other) {
+            return 0;
+        }
+        self.with_lock_pair(other, |this, other_inner| {
+            this.move_tail_to(at, other_inner, other.as_erased())
+        })
+    }
+
+    /// Move the first node matching `pred`, and all nodes after it, to the
+    /// back of `other`.
+    ///
+    /// Returns the number of nodes moved, which is zero if no node matches.
+    /// `pred` is called with both mutexes locked, so it must not access either
+    /// list. See [`PinList::split_off()`] for details.
+    pub fn split_off_by<P>(&'static self, mut pred: P, other: &'static Self) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        if core::ptr::eq(self, other) {
+            return 0;
+        }
+        self.with_lock_pair(other, |this, other_inner| {
+            let Some(at) = this.list.iter().position(|hdr| pred(&hdr.t)) else {
+                return 0;
+            };
+            this.move_tail_to(at, other_inner, other.as_erased())
+        })
+    }
+
+    /// Move all nodes of `other` to the back of this list, leaving `other` empty.
+    ///
+    /// Returns the number of nodes moved. If this list is ordered, it is
+    /// re-sorted afterwards.
+    ///
+    /// Both mutexes are locked for the duration of the call, so no other
+    /// context can observe a node in neither or both lists. See
+    /// [`PinList::split_off()`] for details.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static FROM_ISR: PinList<CsRm, u64> = PinList::new();
+    /// static THREAD: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&THREAD, 1));
+    /// let node_b = pin!(Node::new_for(&FROM_ISR, 2));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// assert_eq!(1, THREAD.append(&FROM_ISR));
+    ///
+    /// let items = THREAD.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[1, 2], items.as_slice());
+    /// assert!(FROM_ISR.is_empty());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn append(&'static self, other: &'static Self) -> usize {
+        other.split_off(0, self)
+    }
+
+    /// Compute a fingerprint of the structure of the list.
+    ///
+    /// This is a CRC-32 over the addresses of all attached nodes, in order. It
+    /// does not depend on the contents of the nodes, so it only changes when
+    /// nodes are attached, removed, or reordered. Comparing fingerprints over
+    /// time can be used to detect unexpected changes to the list.
+    ///
+    /// The blocking mutex is locked while all nodes are visited.
+    ///
+    /// Requires the `integrity` feature.
+    #[cfg(feature = "integrity")]
+    pub fn fingerprint(&self) -> u32 {
+        self.inner.with_lock(|inner| {
+            let crc = inner.list.iter().fold(!0u32, |crc, hdr| {
+                let addr = core::ptr::from_ref(hdr) as usize;
+                crc32_update(crc, &addr.to_ne_bytes())
+            });
+            !crc
+        })
+    }
+
+    /// Check the structure of the list, panicking if it is inconsistent.
+    ///
+    /// This checks that all links between nodes are consistent, and that every
+    /// node agrees that it is linked into this list.
+    ///
+    /// The blocking mutex is locked while all nodes are visited.
+    ///
+    /// Requires the `integrity` feature.
+    #[cfg(feature = "integrity")]
+    pub fn assert_valid(&self) {
+        self.inner.with_lock(|inner| {
+            inner.list.assert_valid();
+            for hdr in inner.list.iter() {
+                assert_eq!(
+                    hdr.linked_to.load(Ordering::Acquire),
+                    self.as_erased(),
+                    "node is not linked to this list"
+                );
+            }
+        })
+    }
+
+    /// Lock both this list and `other`, in order of address.
+    ///
+    /// `self` and `other` must not be the same list.
+    fn with_lock_pair<U, F>(&self, other: &Self, f: F) -> U
+    where
+        F: FnOnce(&mut PinListInner<T>, &mut PinListInner<T>) -> U,
+    {
+        debug_assert!(!core::ptr::eq(self, other));
+        if (self as *const Self) < (other as *const Self) {
+            self.inner
+                .with_lock(|this| other.inner.with_lock(|other| f(this, other)))
+        } else {
+            other
+                .inner
+                .with_lock(|other| self.inner.with_lock(|this| f(this, other)))
+        }
+    }
+
+    /// The type-erased address of this list, as stored in the nodes linked into it.
+    pub(crate) fn as_erased(&self) -> *mut () {
+        (self as *const Self).cast_mut().cast()
+    }
 }
 
 impl<R: ScopedRawMutex, T: Unpin> PinList<R, T> {
@@ -91,10 +1305,130 @@ impl<R: ScopedRawMutex, T: Unpin> PinList<R, T> {
     {
         self.inner.with_lock(|inner| {
             f(IterMut {
-                iter: inner.list.iter_mut(),
+                iter: IterPinMut {
+                    iter: inner.list.iter_raw(),
+                },
             })
         })
     }
+
+    /// Call the given closure with an [`IterMut`] which iterates over
+    /// `&mut T`s, from the back of the list to the front
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_iter_mut_rev<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(Rev<IterMut<'a, T>>) -> U,
+    {
+        self.with_iter_mut(|iter| f(iter.rev()))
+    }
+
+    /// Detach every node for which `f` returns `false`, visiting nodes in order.
+    ///
+    /// Detached nodes stay detached until they are attached again by their
+    /// owner, and dropping them will not need to lock the list. Returns the
+    /// number of nodes that were detached.
+    ///
+    /// The blocking mutex is locked for the duration of the call.
+    ///
+    /// If your type does NOT implement [`Unpin`], consider using
+    /// [`PinList::with_retain_pin_mut()`] which provides `Pin<&mut T>`s.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // (subscriber id, missed heartbeats)
+    /// static LIST: PinList<CsRm, (u32, u8)> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, (1, 0)));
+    /// let node_b = pin!(Node::new_for(&LIST, (2, 3)));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// // Evict stale subscribers
+    /// assert_eq!(1, LIST.with_retain(|s| s.1 < 3));
+    ///
+    /// let items = LIST.with_iter(|n| n.map(|s| s.0).collect::<Vec<_>>());
+    /// assert_eq!(&[1], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_retain<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.with_retain_pin_mut(|t| f(Pin::into_inner(t)))
+    }
+
+    /// Call `f` with each node in order as a `&mut T`, stopping early if it
+    /// returns [`ControlFlow::Break`].
+    ///
+    /// Returns the `Break` value, or `Continue` if every node was visited.
+    ///
+    /// The blocking mutex is locked until all nodes are visited or `f` breaks.
+    pub fn with_try_for_each_mut<B, F>(&self, f: F) -> ControlFlow<B>
+    where
+        F: FnMut(&mut T) -> ControlFlow<B>,
+    {
+        self.with_iter_mut(|mut iter| iter.try_for_each(f))
+    }
+
+    /// Call the given closure with each pair of adjacent nodes, as `&mut T`s
+    ///
+    /// For a list of `[a, b, c]`, `f` is called with `(a, b)` and then `(b, c)`.
+    /// `f` is not called if there are fewer than two nodes.
+    ///
+    /// The blocking mutex is locked for the duration of the call.
+    ///
+    /// If your type does NOT implement [`Unpin`], consider using
+    /// [`PinList::with_iter_pairs_pin_mut()`] which provides `Pin<&mut T>`s.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // (start, len) ranges
+    /// static LIST: PinList<CsRm, (u32, u32)> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, (0, 4)));
+    /// let node_b = pin!(Node::new_for(&LIST, (4, 2)));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// // Coalesce adjacent ranges into the first
+    /// LIST.with_iter_pairs_mut(|a, b| {
+    ///     if a.0 + a.1 == b.0 {
+    ///         a.1 += b.1;
+    ///         b.1 = 0;
+    ///     }
+    /// });
+    ///
+    /// let items = LIST.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[(0, 6), (4, 0)], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_iter_pairs_mut<F>(&self, mut f: F)
+    where
+        F: FnMut(&mut T, &mut T),
+    {
+        self.with_iter_pairs_pin_mut(|a, b| f(Pin::into_inner(a), Pin::into_inner(b)))
+    }
 }
 
 impl<R: ScopedRawMutex + ConstInit, T> PinList<R, T> {
@@ -103,7 +1437,7 @@ impl<R: ScopedRawMutex + ConstInit, T> PinList<R, T> {
     /// Requires that the mutex implements the [`ConstInit`] trait.
     pub const fn new() -> Self {
         Self {
-            inner: BlockingMutex::new(PinListInner { list: List::new() }),
+            inner: BlockingMutex::new(PinListInner::new(None)),
         }
     }
 }
@@ -114,7 +1448,7 @@ impl<R: ScopedRawMutex, T> PinList<R, T> {
     /// Mainly useful when your mutex cannot be created in const context.
     pub const fn new_manual(r: R) -> Self {
         Self {
-            inner: BlockingMutex::const_new(r, PinListInner { list: List::new() }),
+            inner: BlockingMutex::const_new(r, PinListInner::new(None)),
         }
     }
 }
@@ -125,6 +1459,39 @@ impl<R: ScopedRawMutex + ConstInit, T> Default for PinList<R, T> {
     }
 }
 
+/// Formats the items of all attached nodes as a list.
+///
+/// The blocking mutex is locked while formatting, so this must not be used
+/// from within a closure that already holds it.
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{PinList, Node};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<CsRm, u64> = PinList::new();
+///
+/// let node_a = pin!(Node::new_for(&LIST, 123));
+/// let node_b = pin!(Node::new_for(&LIST, 456));
+/// let _hdl_a = node_a.attach();
+/// let _hdl_b = node_b.attach();
+///
+/// assert_eq!("[123, 456]", format!("{LIST:?}"));
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+impl<R: ScopedRawMutex, T: core::fmt::Debug> core::fmt::Debug for PinList<R, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.with_iter(|iter| f.debug_list().entries(iter).finish())
+    }
+}
+
 // SAFETY: Access is mediated through a mutex which prevents aliasing access
 // If the item is Send, it is safe to implement Send for PinList.
 //
@@ -139,6 +1506,13 @@ unsafe impl<R: ScopedRawMutex, T: Send> Sync for PinList<R, T> {}
 
 // ---- impl Iter ----
 
+impl<T> Iter<'_, T> {
+    /// Stop iterating once `stop` is set. See [`StopOn`] for more details.
+    pub fn stop_on(self, stop: &AtomicBool) -> StopOn<'_, Self> {
+        StopOn { iter: self, stop }
+    }
+}
+
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
@@ -147,30 +1521,137 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|ptr| &ptr.t)
+    }
+}
+
+// ---- impl IterAttachedAt ----
+
+#[cfg(feature = "attach-location")]
+impl<'a, T> Iterator for IterAttachedAt<'a, T> {
+    type Item = (&'static Location<'static>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|ptr| {
+            let location = ptr
+                .attached_at
+                .expect("attached nodes always have a location");
+            (location, &ptr.t)
+        })
+    }
+}
+
+#[cfg(feature = "attach-location")]
+impl<T> DoubleEndedIterator for IterAttachedAt<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|ptr| {
+            let location = ptr
+                .attached_at
+                .expect("attached nodes always have a location");
+            (location, &ptr.t)
+        })
+    }
+}
+
+// ---- impl IterMeta ----
+
+#[cfg(feature = "metadata")]
+impl<'a, T> Iterator for IterMeta<'a, T> {
+    type Item = (&'a NodeMeta, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|ptr| (&ptr.meta, &ptr.t))
+    }
+}
+
+#[cfg(feature = "metadata")]
+impl<T> DoubleEndedIterator for IterMeta<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|ptr| (&ptr.meta, &ptr.t))
+    }
+}
+
+// ---- impl IterEnumerate ----
+
+impl<'a, T> Iterator for IterEnumerate<'a, T> {
+    type Item = (u32, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|ptr| (ptr.index, &ptr.t))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterEnumerate<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|ptr| (ptr.index, &ptr.t))
+    }
+}
+
 // ---- impl IterMut ----
 
+impl<T: Unpin> IterMut<'_, T> {
+    /// Stop iterating once `stop` is set. See [`StopOn`] for more details.
+    pub fn stop_on(self, stop: &AtomicBool) -> StopOn<'_, Self> {
+        StopOn { iter: self, stop }
+    }
+}
+
 impl<'a, T: Unpin> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|ptr| {
-            let this = ptr.project();
-            let this: Pin<&mut T> = this.t;
-            Pin::<&mut T>::into_inner(this)
-        })
+        self.iter.next().map(Pin::into_inner)
+    }
+}
+
+impl<T: Unpin> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(Pin::into_inner)
     }
 }
 
 // ---- impl IterPinMut ----
 
+impl<T> IterPinMut<'_, T> {
+    /// Stop iterating once `stop` is set. See [`StopOn`] for more details.
+    pub fn stop_on(self, stop: &AtomicBool) -> StopOn<'_, Self> {
+        StopOn { iter: self, stop }
+    }
+}
+
 impl<'a, T> Iterator for IterPinMut<'a, T> {
     type Item = Pin<&'a mut T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|ptr| {
-            let this = ptr.project();
-            let this: Pin<&mut T> = this.t;
-            this
-        })
+        // SAFETY: The list is locked for 'a, and each node is yielded at most
+        // once, so this is the only reference to the item.
+        self.iter
+            .next()
+            .map(|hdr| unsafe { NodeHeader::item_pin_mut(hdr) })
+    }
+}
+
+impl<T> DoubleEndedIterator for IterPinMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // SAFETY: As in `next()`
+        self.iter
+            .next_back()
+            .map(|hdr| unsafe { NodeHeader::item_pin_mut(hdr) })
+    }
+}
+
+// ---- impl StopOn ----
+
+impl<I: Iterator> Iterator for StopOn<'_, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stop.load(Ordering::Acquire) {
+            None
+        } else {
+            self.iter.next()
+        }
     }
 }
@@ -0,0 +1,44 @@
+//! Convenience macros for a PinList
+
+/// Create, pin, and attach a [`Node`] in one step, binding the resulting
+/// [`NodeHandle`].
+///
+/// The node is pinned on the stack of the calling scope, and will be removed
+/// from the list when that scope ends.
+///
+/// The node can either be given directly, or as a list and a value to create
+/// the node with.
+///
+/// [`Node`]: crate::blocking::Node
+/// [`NodeHandle`]: crate::blocking::NodeHandle
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use pinlist::{pin_attach, blocking::{PinList, Node}};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<CsRm, u64> = PinList::new();
+///
+/// pin_attach!(let hdl_a = &LIST, 123);
+/// pin_attach!(let hdl_b = Node::new_for(&LIST, 456));
+///
+/// assert_eq!(123, hdl_a.with_lock(|a| *a));
+/// assert_eq!(456, hdl_b.with_lock(|b| *b));
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+#[macro_export]
+macro_rules! pin_attach {
+    (let $hdl:pat = $list:expr, $val:expr $(,)?) => {
+        $crate::pin_attach!(let $hdl = $crate::blocking::Node::new_for($list, $val))
+    };
+    (let $hdl:pat = $node:expr) => {
+        let node = ::core::pin::pin!($node);
+        let $hdl = node.attach();
+    };
+}
@@ -68,8 +68,71 @@
 //! # example();
 //! ```
 
+mod chunked;
+mod collect;
+mod context;
+mod cursor;
+mod dump;
+mod erased;
+mod eviction;
+mod id;
+mod keyed;
 mod list;
+mod macros;
 mod node;
+mod observer;
+mod ordered;
+mod peers;
+mod raw;
+mod region;
+mod relative;
+mod round_robin;
+#[cfg(feature = "alloc")]
+mod shared;
+mod snapshot;
+mod split;
+#[cfg(feature = "static_cell")]
+mod static_node;
+mod try_lock;
+mod view;
+mod watermark;
+mod weak;
 
-pub use list::{Iter, IterMut, IterPinMut, PinList};
-pub use node::{Node, NodeHandle};
+pub use chunked::{ChunkToken, ListModified};
+pub use context::ContextPinList;
+pub use cursor::Cursor;
+pub use erased::ErasedNodeHandle;
+pub use eviction::WaitDetachRequested;
+pub use id::{IterIds, NodeId};
+pub use keyed::{Handler, Keyed};
+#[cfg(feature = "attach-location")]
+pub use list::IterAttachedAt;
+#[cfg(feature = "metadata")]
+pub use list::IterMeta;
+pub use list::{Iter, IterEnumerate, IterMut, IterPinMut, PinList, StopOn};
+#[cfg(feature = "metadata")]
+pub use node::NodeMeta;
+pub use node::{AttachError, AttachPosition, Duplicate, Node, NodeHandle};
+pub use observer::NodeObserver;
+pub use peers::IterPeers;
+#[cfg(feature = "chaos")]
+pub use raw::ChaosRawMutex;
+pub use raw::CsPinList;
+#[cfg(feature = "lock_api")]
+pub use raw::LockApiRawMutex;
+#[cfg(feature = "parking_lot")]
+pub use raw::{ParkingLotPinList, ParkingLotRawMutex};
+pub use region::{RegionEntry, RegionIter, RegionIterPinMut};
+pub use round_robin::{IterRoundRobin, IterRoundRobinPinMut};
+#[cfg(feature = "alloc")]
+pub use shared::ArcNodeHandle;
+pub use snapshot::SnapshotError;
+#[cfg(feature = "std")]
+pub use snapshot::{Snapshot, SnapshotEntry, decode_snapshot};
+pub use split::{ReadHandle, WriteHandle};
+#[cfg(feature = "static_cell")]
+pub use static_node::StaticNode;
+pub use try_lock::WouldBlock;
+pub use view::{DynIter, DynView, MapIter, MapView};
+pub use watermark::Watermark;
+pub use weak::WeakNodeHandle;
@@ -1,26 +1,38 @@
 //! The Node of a PinList
 
+#[cfg(feature = "attach-location")]
+use core::panic::Location;
 use core::{
     marker::PhantomData,
+    mem,
     pin::Pin,
-    ptr::{NonNull, addr_of, addr_of_mut},
+    ptr::{self, NonNull, addr_of, addr_of_mut},
+    sync::atomic::{AtomicBool, AtomicPtr, Ordering},
 };
 
-use cordyceps::{Linked, list::Links};
+use cordyceps::{Linked, List, list::Links};
 use mutex::ScopedRawMutex;
 use pin_project::pin_project;
 
-use super::list::PinList;
+use super::{
+    eviction::DetachWaker,
+    list::{PinList, PinListInner, insert_sorted_by},
+};
 
 /// A Node that can be added to a [`PinList`].
 ///
 /// Can be attached to a [`PinList`] by calling [`Node::attach()`] after
-/// pinning, which will return a [`NodeHandle`].
+/// pinning, which will return a [`NodeHandle`]. Nodes created with
+/// [`Node::new()`] are attached by calling [`Node::attach_to()`] instead.
 ///
 /// Pinning the node is essential to ensure that the destructor cannot be
 /// skipped, as the node is unlinked when `Drop` is called, taking the
 /// mutex for a short time to remove the node.
 ///
+/// With the `cache-padded` feature, each node is aligned and padded to 64
+/// bytes, the cache line size of most multi-core targets, so that adjacent
+/// nodes used from different cores do not false-share.
+///
 /// [`PinList`]: crate::blocking::PinList
 ///
 /// ## Example
@@ -52,7 +64,25 @@ use super::list::PinList;
 #[must_use = "Nodes must be `attach()`ed to be added to the list"]
 pub struct Node<'list, R: ScopedRawMutex, T> {
     hdr: NodeHeader<T>,
-    list: &'list PinList<R, T>,
+    /// The list this node is attached to by [`Node::attach()`], or `None` if
+    /// it was created with [`Node::new()`] and has not been bound to one yet
+    list: Option<&'list PinList<R, T>>,
+}
+
+/// Optional owner metadata attached to a [`Node`]
+///
+/// This is not used by the list itself, but can be useful for identifying
+/// which part of a program a node belongs to when debugging.
+///
+/// Requires the `metadata` feature.
+#[cfg(feature = "metadata")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NodeMeta {
+    /// A human readable name for the owner of this node
+    pub name: Option<&'static str>,
+    /// An identifier for the task that owns this node
+    pub task_id: Option<u32>,
 }
 
 /// A handle that represents the [`Node`]s presence in a [`PinList`].
@@ -64,15 +94,81 @@ pub struct NodeHandle<'list, 'node, R: ScopedRawMutex, T> {
     _this: PhantomData<&'node mut Node<'list, R, T>>,
 }
 
+/// Where in a [`PinList`] a node is attached
+///
+/// Used with [`Node::attach_at()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AttachPosition {
+    /// At the front of the list, visited first when iterating
+    Front,
+    /// At the back of the list, visited last when iterating
+    #[default]
+    Back,
+}
+
+/// The error returned by [`Node::attach_unique()`] when an equal node is
+/// already attached.
+///
+/// Contains the node, which was not attached.
+pub struct Duplicate<'list, 'node, R: ScopedRawMutex, T> {
+    node: Pin<&'node mut Node<'list, R, T>>,
+}
+
+/// The error returned by [`Node::try_attach()`] when the list has been
+/// closed.
+///
+/// Contains the node, which was not attached.
+pub struct AttachError<'list, 'node, R: ScopedRawMutex, T> {
+    node: Pin<&'node mut Node<'list, R, T>>,
+}
+
 /// The portions of the Node that are NOT generic over the lifetime or Mutex
 ///
 /// This is the actual item that appears within the cordyceps linked list, to
 /// avoid impossible lifetimes in the PinList itself.
 ///
 /// This header allows for structural pinning of the `T` it contains.
+///
+/// The list never creates a `&mut NodeHeader` for a linked node: mutable
+/// access to the item goes through [`NodeHeader::item_pin_mut()`] instead.
+/// This allows the owner of the node to read the atomics of the header, e.g.
+/// in [`NodeHandle::is_attached()`], without holding the lock.
+///
+/// With the `cache-padded` feature, the header is aligned to 64 bytes, so
+/// that nodes used from different cores do not share a cache line.
 #[pin_project]
+#[cfg_attr(feature = "cache-padded", repr(align(64)))]
 pub(crate) struct NodeHeader<T> {
     pub(crate) links: Links<NodeHeader<T>>,
+    /// The `PinList` this node is currently linked into, or null if it is
+    /// not linked into any list.
+    ///
+    /// This may differ from the list the `Node` was created for, if the node
+    /// was moved between lists. It is only ever modified while holding the
+    /// lock of the list it points to, and of the list it previously pointed to.
+    pub(crate) linked_to: AtomicPtr<()>,
+    /// The index assigned to this node by the list, the most recent time it
+    /// was attached
+    pub(crate) index: u32,
+    /// Stamped from a counter of the list each time the node is linked into
+    /// it, whether by attaching or by moving from another list, so no two
+    /// nodes linked into the same list at different times share one
+    pub(crate) generation: u64,
+    /// The priority of this node. Nodes with a higher priority are attached
+    /// in front of nodes with a lower priority.
+    pub(crate) prio: u8,
+    /// Set by the list to ask the owner of this node to detach it. Cleared
+    /// each time the node is attached.
+    pub(crate) detach_requested: AtomicBool,
+    /// Woken when `detach_requested` is set
+    pub(crate) detach_waker: DetachWaker,
+    /// The call site of the most recent [`Node::attach()`]
+    #[cfg(feature = "attach-location")]
+    pub(crate) attached_at: Option<&'static Location<'static>>,
+    /// The owner metadata of this node
+    #[cfg(feature = "metadata")]
+    pub(crate) meta: NodeMeta,
     #[pin]
     pub(crate) t: T,
 }
@@ -80,23 +176,553 @@ pub(crate) struct NodeHeader<T> {
 impl<'list, R: ScopedRawMutex, T> Node<'list, R, T> {
     /// Create a new [`Node`] for the given [`PinList`](crate::blocking::PinList).
     pub const fn new_for(list: &'list PinList<R, T>, t: T) -> Self {
+        let mut node = Self::new(t);
+        node.list = Some(list);
+        node
+    }
+
+    /// Create a new [`Node`] with the given priority for the given
+    /// [`PinList`](crate::blocking::PinList).
+    ///
+    /// When attached with [`Node::attach()`] or [`Node::attach_front()`], a
+    /// node is placed at the back or the front of the nodes with the same
+    /// priority, behind all nodes with a higher priority, so iteration visits
+    /// the highest priority nodes first. Nodes created with
+    /// [`Node::new_for()`] have priority zero, so a list where all nodes have
+    /// the same priority behaves as if priorities were not used.
+    ///
+    /// Attach methods that choose their own position, such as
+    /// [`Node::attach_sorted()`], or lists that keep their own order, ignore
+    /// the priority.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, &str> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, "idle"));
+    /// let node_b = pin!(Node::new_for_prio(&LIST, 5, "urgent"));
+    /// let node_c = pin!(Node::new_for_prio(&LIST, 1, "normal"));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let _hdl_c = node_c.attach();
+    ///
+    /// let items = LIST.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&["urgent", "normal", "idle"], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub const fn new_for_prio(list: &'list PinList<R, T>, prio: u8, t: T) -> Self {
+        let mut node = Self::new_for(list, t);
+        node.hdr.prio = prio;
+        node
+    }
+
+    /// Create a new [`Node`] that is not yet bound to a list.
+    ///
+    /// The list is chosen when the node is attached with [`Node::attach_to()`].
+    /// Until then, the other attach methods panic.
+    pub const fn new(t: T) -> Self {
         Self {
             hdr: NodeHeader {
                 links: Links::new(),
+                linked_to: AtomicPtr::new(ptr::null_mut()),
+                index: 0,
+                generation: 0,
+                prio: 0,
+                detach_requested: AtomicBool::new(false),
+                detach_waker: DetachWaker::new(),
+                #[cfg(feature = "attach-location")]
+                attached_at: None,
+                #[cfg(feature = "metadata")]
+                meta: NodeMeta {
+                    name: None,
+                    task_id: None,
+                },
                 t,
             },
-            list,
+            list: None,
         }
     }
 
+    /// Initialize a [`Node`] for `list` in place in `slot`.
+    ///
+    /// `init` is called first, with the uninitialized item inside `slot`, and
+    /// must return a reference to that same item once it is initialized. The
+    /// rest of the node is written afterwards, so if `init` panics, nothing in
+    /// `slot` needs to be dropped.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `init` returns a reference to anything other than the item
+    /// it was given.
+    #[cfg(feature = "static_cell")]
+    pub(crate) fn init_in_place<'a, F>(
+        slot: &'a mut mem::MaybeUninit<Self>,
+        list: &'list PinList<R, T>,
+        init: F,
+    ) -> &'a mut Self
+    where
+        F: FnOnce(&mut mem::MaybeUninit<T>) -> &mut T,
+    {
+        let this = slot.as_mut_ptr();
+        // SAFETY: All pointers are derived from `slot`, which is valid for
+        // writes. The item is initialized by `init`, which we check returned
+        // the item it was given, and every other field is written below, so
+        // the node is fully initialized once we assume so.
+        unsafe {
+            let hdr = addr_of_mut!((*this).hdr);
+            let t = addr_of_mut!((*hdr).t);
+            assert!(
+                ptr::eq(init(&mut *t.cast::<mem::MaybeUninit<T>>()), t),
+                "init must return the item it was given"
+            );
+            addr_of_mut!((*hdr).links).write(Links::new());
+            addr_of_mut!((*hdr).linked_to).write(AtomicPtr::new(ptr::null_mut()));
+            addr_of_mut!((*hdr).index).write(0);
+            addr_of_mut!((*hdr).generation).write(0);
+            addr_of_mut!((*hdr).prio).write(0);
+            addr_of_mut!((*hdr).detach_requested).write(AtomicBool::new(false));
+            addr_of_mut!((*hdr).detach_waker).write(DetachWaker::new());
+            #[cfg(feature = "attach-location")]
+            addr_of_mut!((*hdr).attached_at).write(None);
+            #[cfg(feature = "metadata")]
+            addr_of_mut!((*hdr).meta).write(NodeMeta {
+                name: None,
+                task_id: None,
+            });
+            addr_of_mut!((*this).list).write(Some(list));
+            slot.assume_init_mut()
+        }
+    }
+
+    /// Set the owner metadata of this [`Node`].
+    ///
+    /// Requires the `metadata` feature.
+    #[cfg(feature = "metadata")]
+    pub const fn with_meta(mut self, meta: NodeMeta) -> Self {
+        self.hdr.meta = meta;
+        self
+    }
+
+    /// The owner metadata of this [`Node`].
+    ///
+    /// Requires the `metadata` feature.
+    #[cfg(feature = "metadata")]
+    pub const fn meta(&self) -> &NodeMeta {
+        &self.hdr.meta
+    }
+
+    /// Is this node attached to a list?
+    ///
+    /// The list is not locked, so the answer may be out of date as soon as
+    /// it is returned, if another context is detaching or moving the node.
+    pub fn is_attached(&self) -> bool {
+        !self.hdr.linked_to.load(Ordering::Acquire).is_null()
+    }
+
+    /// Consume the node, and return its item.
+    ///
+    /// A node that is not pinned has never been attached, since attaching
+    /// requires pinning it in place until it is dropped, so no lock is needed.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, String> = PinList::new();
+    ///
+    /// let node = Node::new_for(&LIST, String::from("hello"));
+    /// assert_eq!("hello", node.into_inner());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn into_inner(self) -> T {
+        let this = mem::ManuallyDrop::new(self);
+        // SAFETY: The node is not linked into any list, so skipping its `Drop`
+        // impl does not leave any dangling links, and none of the other fields
+        // of the header need to be dropped. `this` is never used again, so the
+        // item is only read out once.
+        unsafe { ptr::read(&this.hdr.t) }
+    }
+
     /// Attach the given node to the list it was created with.
     ///
+    /// Panics if the node was created with [`Node::new()`], and has not been
+    /// bound to a list with [`Node::attach_to()`].
+    ///
     /// This will return a [`NodeHandle`]. The item will remain in the list
-    /// until the `Node` is dropped.
+    /// until the `Node` is dropped, or [`NodeHandle::detach()`] is called.
+    ///
+    /// The mutex will be locked briefly to insert the node in the list. If
+    /// the node is already attached, it is left where it is.
     ///
-    /// The mutex will be locked briefly to insert the node in the list.
+    /// With the `attach-location` feature enabled, the call site of this
+    /// function is recorded, and can be retrieved with
+    /// [`NodeHandle::attached_at()`] or [`PinList::with_iter_attached_at()`].
+    ///
+    /// [`PinList::with_iter_attached_at()`]: crate::blocking::PinList::with_iter_attached_at
+    #[cfg_attr(feature = "attach-location", track_caller)]
     pub fn attach<'node>(self: Pin<&'node mut Self>) -> NodeHandle<'list, 'node, R, T> {
-        let list = self.as_ref().list;
+        self.attach_at(AttachPosition::Back)
+    }
+
+    /// Bind the given node to `list`, and attach it to the back of it.
+    ///
+    /// This is how nodes created with [`Node::new()`] are attached, but any
+    /// node that is not attached may be moved to a different list this way.
+    /// Once bound, the other attach methods use this list. If the node is
+    /// already attached, it is left where it is, and is not bound to `list`.
+    /// Otherwise this behaves like [`Node::attach()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static FAST: PinList<CsRm, u64> = PinList::new();
+    /// static SLOW: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// // The node is built before we know which list it belongs to
+    /// let node_a = pin!(Node::new(123));
+    /// let use_fast = true;
+    /// let _hdl_a = node_a.attach_to(if use_fast { &FAST } else { &SLOW });
+    ///
+    /// assert_eq!(1, FAST.len());
+    /// assert_eq!(0, SLOW.len());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn attach_to<'node>(
+        mut self: Pin<&'node mut Self>,
+        list: &'list PinList<R, T>,
+    ) -> NodeHandle<'list, 'node, R, T> {
+        if !self.is_attached() {
+            // SAFETY: The list is not structurally pinned. No handle to this
+            // node exists, as we hold its mutable reference, and it is not
+            // linked into any list.
+            unsafe { self.as_mut().get_unchecked_mut().list = Some(list) };
+        }
+        self.attach()
+    }
+
+    /// Attach the given node to the front of the list it was created with.
+    ///
+    /// Nodes attached this way are visited before all other nodes of the same
+    /// priority when iterating, so the most recent registration is seen first.
+    /// Otherwise this behaves like [`Node::attach()`].
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn attach_front<'node>(self: Pin<&'node mut Self>) -> NodeHandle<'list, 'node, R, T> {
+        self.attach_at(AttachPosition::Front)
+    }
+
+    /// Attach the given node to the list it was created with, unless the list
+    /// has been closed with [`PinList::close()`].
+    ///
+    /// If the list is closed, the node is NOT attached, and is returned in the
+    /// [`AttachError`]. Otherwise this behaves like [`Node::attach()`].
+    ///
+    /// Note that [`Node::attach()`] and the other attach methods do not check
+    /// whether the list is closed.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let node_b = pin!(Node::new_for(&LIST, 456));
+    /// assert!(node_a.try_attach().is_ok());
+    ///
+    /// // Shutting down
+    /// LIST.close();
+    /// assert!(node_b.try_attach().is_err());
+    /// assert_eq!(1, LIST.len());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn try_attach<'node>(
+        self: Pin<&'node mut Self>,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, AttachError<'list, 'node, R, T>> {
+        let list = self.as_ref().bound_list();
+        #[cfg(feature = "attach-location")]
+        let location = Location::caller();
+        list.inner.with_lock(|inner| {
+            if inner.closed {
+                return Err(AttachError { node: self });
+            }
+            // SAFETY: We hold the lock of the list this node was created for
+            let res = unsafe {
+                self.attach_inner(
+                    inner,
+                    |list, hdr| {
+                        // The node is live, and is not yet visible to anyone else
+                        insert_by_prio(list, hdr, AttachPosition::Back);
+                        true
+                    },
+                    #[cfg(feature = "attach-location")]
+                    location,
+                )
+            };
+            inner.check_watermarks();
+            match res {
+                Ok(hdl) => Ok(hdl),
+                Err(_) => unreachable!("pushing always attaches"),
+            }
+        })
+    }
+
+    /// Attach the given node at the given end of the list it was created with.
+    ///
+    /// Otherwise this behaves like [`Node::attach()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{AttachPosition, Node, PinList};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let node_b = pin!(Node::new_for(&LIST, 456));
+    /// let _hdl_a = node_a.attach_at(AttachPosition::Back);
+    /// let _hdl_b = node_b.attach_at(AttachPosition::Front);
+    ///
+    /// let items = LIST.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[456, 123], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn attach_at<'node>(
+        self: Pin<&'node mut Self>,
+        position: AttachPosition,
+    ) -> NodeHandle<'list, 'node, R, T> {
+        let res = self.attach_with(|list, hdr| {
+            // SAFETY: The node is live, and is not yet visible to anyone else
+            unsafe { insert_by_prio(list, hdr, position) };
+            true
+        });
+        match res {
+            Ok(hdl) => hdl,
+            Err(_) => unreachable!("pushing always attaches"),
+        }
+    }
+
+    /// Attach the given node to the list it was created with, unless an equal
+    /// node is already attached.
+    ///
+    /// If an equal node is present, the node is NOT attached, and is returned
+    /// in the [`Duplicate`] error. Otherwise this behaves like [`Node::attach()`].
+    ///
+    /// The mutex will be locked while the list is searched for an equal node.
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn attach_unique<'node>(
+        self: Pin<&'node mut Self>,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, Duplicate<'list, 'node, R, T>>
+    where
+        T: PartialEq,
+    {
+        self.attach_with(|list, hdr| {
+            // SAFETY: The node is live, and is not yet visible to anyone else
+            let t = unsafe { &*addr_of!((*hdr.as_ptr()).t) };
+            if list.iter().any(|other| other.t == *t) {
+                return false;
+            }
+            // SAFETY: The node is live, and is not yet visible to anyone else
+            unsafe { insert_by_prio(list, hdr, AttachPosition::Back) };
+            true
+        })
+        .map_err(|node| Duplicate { node })
+    }
+
+    /// Attach the given node to the list it was created with, after all nodes
+    /// that are not greater than it.
+    ///
+    /// If all nodes are attached this way, the list stays sorted, with equal
+    /// nodes in the order they were attached. Otherwise this behaves like
+    /// [`Node::attach()`].
+    ///
+    /// The mutex will be locked while the list is searched for the position.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // Deadlines
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 300));
+    /// let node_b = pin!(Node::new_for(&LIST, 100));
+    /// let node_c = pin!(Node::new_for(&LIST, 200));
+    /// let _hdl_a = node_a.attach_sorted();
+    /// let _hdl_b = node_b.attach_sorted();
+    /// let _hdl_c = node_c.attach_sorted();
+    ///
+    /// let items = LIST.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[100, 200, 300], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn attach_sorted<'node>(self: Pin<&'node mut Self>) -> NodeHandle<'list, 'node, R, T>
+    where
+        T: Ord,
+    {
+        let res = self.attach_with(|list, hdr| {
+            // SAFETY: The node is live, and is not yet visible to anyone else
+            unsafe { insert_sorted_by(list, hdr, T::cmp) };
+            true
+        });
+        match res {
+            Ok(hdl) => hdl,
+            Err(_) => unreachable!("sorted insertion always attaches"),
+        }
+    }
+
+    /// Attach the given node to the list it was created with, using `insert`
+    /// to link the node into the list.
+    ///
+    /// `insert` is called with the mutex locked, and returns whether it linked
+    /// the node. If it did not, the node is returned back. If the node is
+    /// already attached, `insert` is not called. If the list is ordered, the
+    /// node is moved to its sorted position after `insert` links it.
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub(crate) fn attach_with<'node, F>(
+        self: Pin<&'node mut Self>,
+        insert: F,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, Pin<&'node mut Self>>
+    where
+        F: FnOnce(&mut List<NodeHeader<T>>, NonNull<NodeHeader<T>>) -> bool,
+    {
+        let list = self.as_ref().bound_list();
+        #[cfg(feature = "attach-location")]
+        let location = Location::caller();
+        list.inner.with_lock(|inner| {
+            // SAFETY: We hold the lock of the list this node was created for
+            let res = unsafe {
+                self.attach_inner(
+                    inner,
+                    insert,
+                    #[cfg(feature = "attach-location")]
+                    location,
+                )
+            };
+            inner.check_watermarks();
+            res
+        })
+    }
+
+    /// Attach the given node to the list it was created with, using `insert`
+    /// to link the node into the list, given the locked inner state of that
+    /// list.
+    ///
+    /// This behaves like [`Node::attach_with()`], except that the caller is
+    /// responsible for checking the watermarks of the list.
+    ///
+    /// # Safety
+    ///
+    /// `inner` must be the inner state of the list this node was created for.
+    unsafe fn attach_inner<'node, F>(
+        self: Pin<&'node mut Self>,
+        inner: &mut PinListInner<T>,
+        insert: F,
+        #[cfg(feature = "attach-location")] location: &'static Location<'static>,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, Pin<&'node mut Self>>
+    where
+        F: FnOnce(&mut List<NodeHeader<T>>, NonNull<NodeHeader<T>>) -> bool,
+    {
+        // SAFETY: The caller guarantees we hold the lock of the list this node
+        // was created for
+        unsafe {
+            self.attach_locked(
+                &mut inner.next_index,
+                &mut inner.next_node_generation,
+                |hdr| {
+                    if !insert(&mut inner.list, hdr) {
+                        return false;
+                    }
+                    if let Some(cmp) = inner.order {
+                        // The node was just linked into this list
+                        inner.list.remove(hdr);
+                        insert_sorted_by(&mut inner.list, hdr, cmp);
+                    }
+                    inner.generation = inner.generation.wrapping_add(1);
+                    true
+                },
+                #[cfg(feature = "attach-location")]
+                location,
+            )
+        }
+    }
+
+    /// Attach the given node to the list it was created with, using `insert`
+    /// to link the node into the list, while already holding the mutex.
+    ///
+    /// `insert` returns whether it linked the node. If it did not, the node is
+    /// returned back. If the node is already attached, `insert` is not called.
+    /// If it is linked, the node is stamped with the list's `next_index` and
+    /// `next_node_generation`.
+    ///
+    /// The caller is responsible for checking the watermarks of the list.
+    ///
+    /// # Safety
+    ///
+    /// The mutex of the list this node was created for must be held.
+    pub(crate) unsafe fn attach_locked<'node, F>(
+        self: Pin<&'node mut Self>,
+        next_index: &mut u32,
+        next_generation: &mut u64,
+        insert: F,
+        #[cfg(feature = "attach-location")] location: &'static Location<'static>,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, Pin<&'node mut Self>>
+    where
+        F: FnOnce(NonNull<NodeHeader<T>>) -> bool,
+    {
+        let list = self.as_ref().bound_list();
         // Safety: We consume the Pin'd version of self, to convert it to a NonNull. We will
         // only ever use this as a pinned item, unless T: Unpin.
         let ptr_self: NonNull<Node<'list, R, T>> =
@@ -106,13 +732,142 @@ impl<'list, R: ScopedRawMutex, T> Node<'list, R, T> {
         // also always valid.
         let ptr_hdr: NonNull<NodeHeader<T>> =
             unsafe { NonNull::new_unchecked(addr_of_mut!((*ptr_self.as_ptr()).hdr)) };
-        list.inner.with_lock(|inner| {
-            inner.list.push_back(ptr_hdr);
-        });
-        NodeHandle {
+        // SAFETY: The node is live, and `linked_to` is only accessed atomically
+        let linked_to = unsafe { &*addr_of!((*ptr_hdr.as_ptr()).linked_to) };
+        if linked_to.load(Ordering::Acquire).is_null() {
+            if !insert(ptr_hdr) {
+                // SAFETY: We were pinned when called, and have not moved
+                return Err(unsafe { Pin::new_unchecked(&mut *ptr_self.as_ptr()) });
+            }
+            // SAFETY: The node was just linked, and the caller holds the lock
+            unsafe {
+                (*ptr_hdr.as_ptr()).index = *next_index;
+                (*ptr_hdr.as_ptr()).generation = *next_generation;
+                (*ptr_hdr.as_ptr())
+                    .detach_requested
+                    .store(false, Ordering::Relaxed);
+                #[cfg(feature = "attach-location")]
+                {
+                    (*ptr_hdr.as_ptr()).attached_at = Some(location);
+                }
+            }
+            *next_index = next_index.wrapping_add(1);
+            *next_generation = next_generation.wrapping_add(1);
+            linked_to.store(list.as_erased(), Ordering::Release);
+        }
+        Ok(NodeHandle {
             this: ptr_self,
             list,
             _this: PhantomData,
+        })
+    }
+
+    /// Is this node the one created for `list`?
+    pub(crate) fn is_for(&self, list: &PinList<R, T>) -> bool {
+        self.list.is_some_and(|l| ptr::eq(l, list))
+    }
+
+    /// The list this node is bound to.
+    ///
+    /// Panics if the node was created with [`Node::new()`], and has not been
+    /// bound with [`Node::attach_to()`].
+    fn bound_list(&self) -> &'list PinList<R, T> {
+        self.list
+            .expect("node is not bound to a list, attach it with `attach_to()`")
+    }
+}
+
+/// Link `hdr` into `list` at the given end of the nodes with the same
+/// priority, behind all nodes with a higher priority.
+///
+/// Only the nodes with a different priority at that end are visited, so if all
+/// nodes have the same priority, this takes constant time.
+///
+/// # Safety
+///
+/// `hdr` must point to a live node that is not linked into any list, and no
+/// other reference to it may exist.
+pub(crate) unsafe fn insert_by_prio<T>(
+    list: &mut List<NodeHeader<T>>,
+    hdr: NonNull<NodeHeader<T>>,
+    position: AttachPosition,
+) {
+    // SAFETY: The caller guarantees the node is live, and not aliased
+    let prio = unsafe { *addr_of!((*hdr.as_ptr()).prio) };
+    match position {
+        AttachPosition::Front => {
+            let mut cursor = list.cursor_front_mut();
+            while cursor.current().is_some_and(|cur| cur.prio > prio) {
+                cursor.move_next();
+            }
+            // At the null position, this inserts at the back
+            cursor.insert_before(hdr);
+        }
+        AttachPosition::Back => {
+            let mut cursor = list.cursor_back_mut();
+            while cursor.current().is_some_and(|cur| cur.prio < prio) {
+                cursor.move_prev();
+            }
+            // At the null position, this inserts at the front
+            cursor.insert_after(hdr);
+        }
+    }
+}
+
+impl<T> NodeHeader<T> {
+    /// Project a pointer to a header to its pinned item, without creating a
+    /// reference to the rest of the header.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be live, and linked into a list whose lock is held for
+    /// `'a`. No other reference to the item may exist for `'a`.
+    pub(crate) unsafe fn item_pin_mut<'a>(this: NonNull<Self>) -> Pin<&'a mut T> {
+        // SAFETY: The caller guarantees the item is live and not otherwise
+        // borrowed, and we only provide a Pin<&mut T>.
+        unsafe { Pin::new_unchecked(&mut *addr_of_mut!((*this.as_ptr()).t)) }
+    }
+
+    /// Call `f` with the inner state of the list this node is currently
+    /// linked into, while holding that list's mutex.
+    ///
+    /// If the node is not linked into any list, no mutex is locked, and `f`
+    /// is returned back.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a live `NodeHeader`, and `R` must be the mutex
+    /// type of the list it is linked into.
+    pub(crate) unsafe fn with_linked_list<R, U, F>(this: NonNull<Self>, f: F) -> Result<U, F>
+    where
+        R: ScopedRawMutex,
+        F: FnOnce(&mut PinListInner<T>) -> U,
+    {
+        // SAFETY: The caller guarantees `this` is live, and `linked_to` is
+        // only accessed atomically. The list never creates a `&mut NodeHeader`,
+        // so this shared reference may coexist with a list holding the lock.
+        let linked_to = unsafe { &*addr_of!((*this.as_ptr()).linked_to) };
+        let mut f = f;
+        loop {
+            let list: *const PinList<R, T> = linked_to.load(Ordering::Acquire).cast();
+            if list.is_null() {
+                return Err(f);
+            }
+            // SAFETY: Nodes are only ever linked into lists that outlive them, and
+            // the pointer is only changed with the lock of the list it points to held,
+            // so if it still matches once we have the lock, it will not change.
+            let list = unsafe { &*list };
+            let res = list.inner.with_lock(|inner| {
+                if ptr::eq(linked_to.load(Ordering::Acquire), list.as_erased()) {
+                    Ok(f(inner))
+                } else {
+                    Err(f)
+                }
+            });
+            match res {
+                Ok(u) => return Ok(u),
+                Err(g) => f = g,
+            }
         }
     }
 }
@@ -141,24 +896,106 @@ unsafe impl<T> Linked<Links<NodeHeader<T>>> for NodeHeader<T> {
     }
 }
 
+impl<'list, 'node, R: ScopedRawMutex, T> Duplicate<'list, 'node, R, T> {
+    /// Recover the node that was not attached
+    pub fn into_node(self) -> Pin<&'node mut Node<'list, R, T>> {
+        self.node
+    }
+}
+
+impl<R: ScopedRawMutex, T> core::fmt::Debug for Duplicate<'_, '_, R, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Duplicate").finish_non_exhaustive()
+    }
+}
+
+impl<'list, 'node, R: ScopedRawMutex, T> AttachError<'list, 'node, R, T> {
+    /// Recover the node that was not attached
+    pub fn into_node(self) -> Pin<&'node mut Node<'list, R, T>> {
+        self.node
+    }
+}
+
+impl<R: ScopedRawMutex, T> core::fmt::Debug for AttachError<'_, '_, R, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AttachError").finish_non_exhaustive()
+    }
+}
+
+/// Shows whether the node is attached, without locking the list.
+impl<R: ScopedRawMutex, T> core::fmt::Debug for Node<'_, R, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Node")
+            .field("attached", &self.is_attached())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Drop the node, unlinking it from the list in the process.
+///
+/// If the node was never attached, or has already been detached, no mutex is
+/// locked.
 impl<R: ScopedRawMutex, T> Drop for Node<'_, R, T> {
     fn drop(&mut self) {
-        // SAFETY: We have the mutex held, meaning we can detach ourselves
-        // from the list.
-        self.list.inner.with_lock(|inner| unsafe {
-            let this = NonNull::from(&mut self.hdr);
-            inner.list.remove(this);
-        })
+        let this = NonNull::from(&mut self.hdr);
+        // SAFETY: We are live for the duration of the call, and we have the
+        // mutex of the list we are linked into held, meaning we can detach
+        // ourselves from that list. If we are not linked, the closure is
+        // returned without locking anything.
+        let _ = unsafe {
+            NodeHeader::with_linked_list::<R, _, _>(this, |inner| {
+                inner.list.remove(this);
+                (*this.as_ptr())
+                    .linked_to
+                    .store(ptr::null_mut(), Ordering::Release);
+                inner.changed();
+            })
+        };
     }
 }
 
-impl<'list, R: ScopedRawMutex, T> NodeHandle<'list, '_, R, T> {
+impl<'list, 'node, R: ScopedRawMutex, T> NodeHandle<'list, 'node, R, T> {
+    /// Call `f` with the mutex of the list this node is linked into locked,
+    /// or the mutex of the list it was created for, if it is not linked.
+    pub(crate) fn with_inner<U, F: FnOnce(&mut PinListInner<T>) -> U>(&self, f: F) -> U {
+        // SAFETY: The handle borrows the node, so it is live, and the node was
+        // created for a list with the same mutex type as any it is linked into.
+        let res = unsafe {
+            let hdr = NonNull::new_unchecked(addr_of_mut!((*self.this.as_ptr()).hdr));
+            NodeHeader::with_linked_list::<R, U, F>(hdr, f)
+        };
+        match res {
+            Ok(u) => u,
+            Err(f) => self.list.inner.with_lock(f),
+        }
+    }
+
+    /// Create a second handle to the same node.
+    ///
+    /// # Safety
+    ///
+    /// Neither handle may be used to give up the node, e.g. with
+    /// [`NodeHandle::detach()`], while the other exists.
+    pub(crate) unsafe fn alias(&self) -> Self {
+        Self {
+            list: self.list,
+            this: self.this,
+            _this: PhantomData,
+        }
+    }
+
+    /// The header of the node this handle refers to.
+    pub(crate) fn hdr(&self) -> NonNull<NodeHeader<T>> {
+        // SAFETY: We know self.this is a valid pointer, so creating a nonnull
+        // of a field is also always valid.
+        unsafe { NonNull::new_unchecked(addr_of_mut!((*self.this.as_ptr()).hdr)) }
+    }
+
     /// Access the immutably item within a closure.
     ///
     /// The mutex is locked for the duration of the closure.
     pub fn with_lock<U, F: FnOnce(&T) -> U>(&self, f: F) -> U {
-        self.list.inner.with_lock(|_inner| {
+        self.with_inner(|_inner| {
             // SAFETY: We hold the lock, and we are providing a &T reference, preventing
             // the item from being moved out
             let this: &T = unsafe {
@@ -180,7 +1017,7 @@ impl<'list, R: ScopedRawMutex, T> NodeHandle<'list, '_, R, T> {
     ///
     /// The mutex is locked for the duration of the closure.
     pub fn with_lock_pin_mut<U, F: FnOnce(Pin<&mut T>) -> U>(&self, f: F) -> U {
-        self.list.inner.with_lock(|_inner| {
+        self.with_inner(|_inner| {
             // SAFETY: We hold the lock, and we are providing a Pin<&mut T> reference, preventing
             // the item from being moved out
             let this: Pin<&mut T> = unsafe {
@@ -195,10 +1032,200 @@ impl<'list, R: ScopedRawMutex, T> NodeHandle<'list, '_, R, T> {
         })
     }
 
+    /// Access a pinned field of the item within a closure.
+    ///
+    /// `project` selects the field, typically using the projection generated
+    /// by [`pin_project`](https://docs.rs/pin-project), and `f` is called with
+    /// only that field, so the rest of the item is not exposed to it.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::{
+    ///     future::{Future, Ready, ready},
+    ///     pin::pin,
+    ///     task::{Context, Poll, Waker},
+    /// };
+    /// use pin_project::pin_project;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// #[pin_project]
+    /// struct Waiter {
+    ///     name: &'static str,
+    ///     #[pin]
+    ///     fut: Ready<u32>,
+    /// }
+    ///
+    /// static LIST: PinList<CsRm, Waiter> = PinList::new();
+    ///
+    /// let node = pin!(Node::new_for(&LIST, Waiter { name: "w", fut: ready(1) }));
+    /// let hdl = node.attach();
+    ///
+    /// // Only the future is visible to the closure
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let res = hdl.with_lock_project(|w| w.project().fut, |fut| fut.poll(&mut cx));
+    /// assert_eq!(Poll::Ready(1), res);
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_lock_project<P: ?Sized, U, F>(
+        &self,
+        project: for<'a> fn(Pin<&'a mut T>) -> Pin<&'a mut P>,
+        f: F,
+    ) -> U
+    where
+        F: FnOnce(Pin<&mut P>) -> U,
+    {
+        self.with_lock_pin_mut(|t| f(project(t)))
+    }
+
+    /// Detach the node from the list it is attached to, and get it back.
+    ///
+    /// The node keeps its item, and can be attached again later, to the
+    /// list it was created for. If the node was already detached, for example
+    /// by [`Cursor::remove_current()`], it is returned as-is.
+    ///
+    /// The mutex is locked briefly to unlink the node.
+    ///
+    /// [`Cursor::remove_current()`]: crate::blocking::Cursor::remove_current
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let mut node_a = pin!(Node::new_for(&LIST, 123));
+    /// let hdl_a = node_a.as_mut().attach();
+    /// assert_eq!(1, LIST.len());
+    ///
+    /// // Suspend...
+    /// let node_a = hdl_a.detach();
+    /// assert_eq!(0, LIST.len());
+    ///
+    /// // ...and resume
+    /// let _hdl_a = node_a.attach();
+    /// assert_eq!(1, LIST.len());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn detach(self) -> Pin<&'node mut Node<'list, R, T>> {
+        let hdr = self.hdr();
+        // SAFETY: The handle borrows the node, so it is live, and the node was
+        // created for a list with the same mutex type as any it is linked into.
+        // We hold the mutex of the list it is linked into, so we can unlink it.
+        let _ = unsafe {
+            NodeHeader::with_linked_list::<R, _, _>(hdr, |inner| {
+                inner.list.remove(hdr);
+                (*hdr.as_ptr())
+                    .linked_to
+                    .store(ptr::null_mut(), Ordering::Release);
+                inner.changed();
+            })
+        };
+        // SAFETY: The handle was created from a pinned reference with the
+        // `'node` lifetime, which it gives up here.
+        unsafe { Pin::new_unchecked(&mut *self.this.as_ptr()) }
+    }
+
     /// Access the list this Node was created with
     pub fn list(&self) -> &'list PinList<R, T> {
         self.list
     }
+
+    /// The priority this node was created with. See [`Node::new_for_prio()`].
+    ///
+    /// The mutex is briefly locked to read the priority.
+    pub fn priority(&self) -> u8 {
+        // SAFETY: We hold the lock, so the list is not accessing the header
+        self.with_inner(|_inner| unsafe { *addr_of!((*self.hdr().as_ptr()).prio) })
+    }
+
+    /// Is the node of this handle still attached to a list?
+    ///
+    /// A node stays attached while its handle exists, unless it is removed
+    /// through the list, for example with [`Cursor::remove_current()`]. The
+    /// list is not locked, so the answer may be out of date as soon as it is
+    /// returned, if another context is detaching or moving the node.
+    ///
+    /// [`Cursor::remove_current()`]: crate::blocking::Cursor::remove_current
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let hdl_a = node_a.attach();
+    /// assert!(hdl_a.is_attached());
+    ///
+    /// LIST.with_retain(|_| false);
+    /// assert!(!hdl_a.is_attached());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn is_attached(&self) -> bool {
+        // SAFETY: The handle borrows the node, so it is live, and `linked_to` is
+        // only accessed atomically
+        let linked_to = unsafe { &(*self.hdr().as_ptr()).linked_to };
+        !linked_to.load(Ordering::Acquire).is_null()
+    }
+
+    /// The index assigned to this node when it was attached.
+    ///
+    /// Each list assigns indices in the order nodes are attached, starting
+    /// from zero, and wrapping on overflow. The index does not change while the
+    /// node stays attached, even if the list is reordered, so it can be used to
+    /// refer to the node across iterations. See [`PinList::with_iter_enumerate()`].
+    ///
+    /// The mutex is briefly locked to read the index, so this must not be
+    /// called while the list is already locked, such as while iterating.
+    pub fn index(&self) -> u32 {
+        // SAFETY: We hold the lock, so the list is not accessing the header
+        self.with_inner(|_inner| unsafe { *addr_of!((*self.hdr().as_ptr()).index) })
+    }
+
+    /// The call site where this node was attached to the list.
+    ///
+    /// Requires the `attach-location` feature.
+    #[cfg(feature = "attach-location")]
+    pub fn attached_at(&self) -> &'static Location<'static> {
+        // SAFETY: We hold the lock, so the list is not accessing the header
+        let location =
+            self.with_inner(|_inner| unsafe { *addr_of!((*self.hdr().as_ptr()).attached_at) });
+        location.expect("attached nodes always have a location")
+    }
+
+    /// The owner metadata of this node.
+    ///
+    /// Requires the `metadata` feature.
+    #[cfg(feature = "metadata")]
+    pub fn meta(&self) -> NodeMeta {
+        // SAFETY: We hold the lock, so the list is not accessing the header
+        self.with_inner(|_inner| unsafe { *addr_of!((*self.hdr().as_ptr()).meta) })
+    }
 }
 
 impl<'list, R: ScopedRawMutex, T: Unpin> NodeHandle<'list, '_, R, T> {
@@ -209,7 +1236,7 @@ impl<'list, R: ScopedRawMutex, T: Unpin> NodeHandle<'list, '_, R, T> {
     ///
     /// The mutex is locked for the duration of the closure.
     pub fn with_lock_mut<U, F: FnOnce(&mut T) -> U>(&self, f: F) -> U {
-        self.list.inner.with_lock(|_inner| {
+        self.with_inner(|_inner| {
             // SAFETY: We hold the lock, and T: Unpin, so it is safe to provide
             // a mutable reference for the duration of the closure
             let this: &mut T = unsafe {
@@ -223,4 +1250,95 @@ impl<'list, R: ScopedRawMutex, T: Unpin> NodeHandle<'list, '_, R, T> {
             f(this)
         })
     }
+
+    /// Replace the item with `t`, returning the old item.
+    ///
+    /// The item must implement `T: Unpin`, as moving it out would otherwise
+    /// break the guarantees of pinning.
+    ///
+    /// The mutex is locked briefly to swap the items.
+    pub fn replace(&self, t: T) -> T {
+        self.with_lock_mut(|old| mem::replace(old, t))
+    }
+
+    /// Take the item, leaving [`Default::default()`] in its place.
+    ///
+    /// This is useful for mailboxes, where each node holds an `Option<Msg>`.
+    /// The item must implement `T: Unpin`, as moving it out would otherwise
+    /// break the guarantees of pinning.
+    ///
+    /// The mutex is locked briefly to swap the items.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static MAILBOXES: PinList<CsRm, Option<u32>> = PinList::new();
+    ///
+    /// let mbox = pin!(Node::new_for(&MAILBOXES, None));
+    /// let hdl = mbox.attach();
+    ///
+    /// // Deliver to every mailbox
+    /// MAILBOXES.with_iter_mut(|n| n.for_each(|m| *m = Some(42)));
+    ///
+    /// assert_eq!(Some(42), hdl.take());
+    /// assert_eq!(None, hdl.take());
+    /// assert_eq!(None, hdl.replace(Some(7)));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn take(&self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+}
+
+/// Handles are equal if they refer to the same node.
+impl<R: ScopedRawMutex, T> PartialEq for NodeHandle<'_, '_, R, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.this == other.this
+    }
+}
+
+impl<R: ScopedRawMutex, T> Eq for NodeHandle<'_, '_, R, T> {}
+
+/// Handles are ordered by the address of the node they refer to.
+impl<R: ScopedRawMutex, T> PartialOrd for NodeHandle<'_, '_, R, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<R: ScopedRawMutex, T> Ord for NodeHandle<'_, '_, R, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.this.cmp(&other.this)
+    }
+}
+
+/// Handles are hashed by the address of the node they refer to.
+impl<R: ScopedRawMutex, T> core::hash::Hash for NodeHandle<'_, '_, R, T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.this.hash(state)
+    }
+}
+
+/// Shows the address of the node, and whether it is still attached, without
+/// locking the list, so handles may be formatted while the list is locked.
+/// Use [`NodeHandle::index()`] for the index.
+impl<R: ScopedRawMutex, T> core::fmt::Debug for NodeHandle<'_, '_, R, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NodeHandle")
+            .field("node", &self.this)
+            .field("attached", &self.is_attached())
+            .finish_non_exhaustive()
+    }
 }
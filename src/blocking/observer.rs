@@ -0,0 +1,77 @@
+//! Read-only access to an attached node
+
+use mutex::ScopedRawMutex;
+
+use super::{id::NodeId, node::NodeHandle};
+
+/// A read-only view of a [`NodeHandle`]
+///
+/// This can be handed to other components that may read the item, but must
+/// not mutate or detach the node. It borrows the handle, and is `Copy`.
+///
+/// Obtained by calling [`NodeHandle::downgrade()`].
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{Node, NodeObserver, PinList};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<CsRm, u64> = PinList::new();
+///
+/// fn report(obs: NodeObserver<'_, '_, '_, CsRm, u64>) -> u64 {
+///     obs.with_lock(|t| *t)
+/// }
+///
+/// let node_a = pin!(Node::new_for(&LIST, 123));
+/// let hdl_a = node_a.attach();
+/// assert_eq!(123, report(hdl_a.downgrade()));
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+pub struct NodeObserver<'h, 'list, 'node, R: ScopedRawMutex, T> {
+    hdl: &'h NodeHandle<'list, 'node, R, T>,
+}
+
+// ---- impl NodeHandle ----
+
+impl<'list, 'node, R: ScopedRawMutex, T> NodeHandle<'list, 'node, R, T> {
+    /// Get a read-only [`NodeObserver`] of this node.
+    pub fn downgrade(&self) -> NodeObserver<'_, 'list, 'node, R, T> {
+        NodeObserver { hdl: self }
+    }
+}
+
+// ---- impl NodeObserver ----
+
+impl<R: ScopedRawMutex, T> NodeObserver<'_, '_, '_, R, T> {
+    /// Access the item immutably within a closure.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    pub fn with_lock<U, F: FnOnce(&T) -> U>(&self, f: F) -> U {
+        self.hdl.with_lock(f)
+    }
+
+    /// Is the node still attached to a list? See [`NodeHandle::is_attached()`].
+    pub fn is_attached(&self) -> bool {
+        self.hdl.is_attached()
+    }
+
+    /// The identity of the node. See [`NodeHandle::id()`].
+    pub fn id(&self) -> NodeId {
+        self.hdl.id()
+    }
+}
+
+impl<R: ScopedRawMutex, T> Clone for NodeObserver<'_, '_, '_, R, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: ScopedRawMutex, T> Copy for NodeObserver<'_, '_, '_, R, T> {}
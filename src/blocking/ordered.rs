@@ -0,0 +1,166 @@
+//! PinLists that keep their nodes sorted
+
+use core::cmp::Ordering;
+
+use mutex::{BlockingMutex, ConstInit, ScopedRawMutex};
+
+use super::{
+    list::{PinList, PinListInner, insert_sorted_by, merge_sort},
+    node::{NodeHandle, NodeHeader},
+};
+
+// ---- impl PinListInner ----
+
+impl<T> PinListInner<T> {
+    /// Sort the nodes, if this is an ordered list.
+    ///
+    /// Must be called after nodes are added by any means other than attaching.
+    pub(crate) fn restore_order(&mut self) {
+        if let Some(mut cmp) = self.order {
+            merge_sort(&mut self.list, &mut cmp);
+        }
+    }
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex + ConstInit, T> PinList<R, T> {
+    /// Create a new [`PinList`] which keeps its nodes sorted by `cmp`.
+    ///
+    /// Every node is attached at its sorted position, after all nodes that are
+    /// not greater than it, regardless of how it is attached. For example,
+    /// [`Node::attach_front()`] on an ordered list behaves like
+    /// [`Node::attach_sorted()`]. A [`Cursor`] refuses to insert a node where
+    /// it would break the order.
+    ///
+    /// Items that are modified in place, e.g. with [`PinList::with_iter_mut()`],
+    /// are not moved. If a modification changes the order of an item, the
+    /// node must be moved with [`NodeHandle::resort()`], or the list re-sorted
+    /// with [`PinList::with_sort_by()`] using the same comparator.
+    ///
+    /// Requires that the mutex implements the [`ConstInit`] trait.
+    ///
+    /// [`Node::attach_front()`]: crate::blocking::Node::attach_front
+    /// [`Node::attach_sorted()`]: crate::blocking::Node::attach_sorted
+    /// [`Cursor`]: crate::blocking::Cursor
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // (deadline, name), ordered by deadline
+    /// static QUEUE: PinList<CsRm, (u64, &'static str)> =
+    ///     PinList::new_ordered(|a, b| a.0.cmp(&b.0));
+    ///
+    /// let node_a = pin!(Node::new_for(&QUEUE, (300, "a")));
+    /// let node_b = pin!(Node::new_for(&QUEUE, (100, "b")));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// assert_eq!(Some("b"), QUEUE.with_peek_first(|t| t.map(|t| t.1)));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub const fn new_ordered(cmp: fn(&T, &T) -> Ordering) -> Self {
+        Self {
+            inner: BlockingMutex::new(PinListInner::new(Some(cmp))),
+        }
+    }
+}
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Create a new [`PinList`] which keeps its nodes sorted by `cmp`, with a
+    /// given [`ScopedRawMutex`].
+    ///
+    /// See [`PinList::new_ordered()`] for details.
+    pub const fn new_ordered_manual(r: R, cmp: fn(&T, &T) -> Ordering) -> Self {
+        Self {
+            inner: BlockingMutex::const_new(r, PinListInner::new(Some(cmp))),
+        }
+    }
+
+    /// Is this list kept sorted? See [`PinList::new_ordered()`].
+    pub fn is_ordered(&self) -> bool {
+        self.inner.with_lock(|inner| inner.order.is_some())
+    }
+
+    /// Call the given closure with the node at the front of the list, or
+    /// `None` if the list is empty.
+    ///
+    /// For an ordered list, this is the minimum node.
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_peek_first<U, F>(&self, f: F) -> U
+    where
+        F: FnOnce(Option<&T>) -> U,
+    {
+        self.inner
+            .with_lock(|inner| f(inner.list.front().map(|hdr| &hdr.get_ref().t)))
+    }
+}
+
+// ---- impl NodeHandle ----
+
+impl<R: ScopedRawMutex, T> NodeHandle<'_, '_, R, T> {
+    /// Move this node to its sorted position, after its item was modified.
+    ///
+    /// On an ordered list, items modified in place are not moved. Calling this
+    /// after changing the key of an item restores the order, without sorting
+    /// the whole list. On a list that is not ordered, this does nothing.
+    ///
+    /// Returns `false` if the node is not attached. The mutex is locked while
+    /// the node is relinked, which takes time proportional to the number of
+    /// nodes.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static QUEUE: PinList<CsRm, u64> = PinList::new_ordered(u64::cmp);
+    ///
+    /// let node_a = pin!(Node::new_for(&QUEUE, 100));
+    /// let node_b = pin!(Node::new_for(&QUEUE, 200));
+    /// let hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// // Postpone A
+    /// hdl_a.with_lock_mut(|t| *t = 300);
+    /// assert!(hdl_a.resort());
+    ///
+    /// let items = QUEUE.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[200, 300], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn resort(&self) -> bool {
+        let hdr = self.hdr();
+        // SAFETY: The handle borrows the node, so it is live, and the node was
+        // created for a list with the same mutex type as any it is linked into.
+        // We hold the mutex of the list it is linked into, so we can relink it,
+        // and no one else can be mutating its item.
+        let res = unsafe {
+            NodeHeader::with_linked_list::<R, _, _>(hdr, |inner| {
+                if let Some(cmp) = inner.order {
+                    inner.list.remove(hdr);
+                    insert_sorted_by(&mut inner.list, hdr, cmp);
+                    inner.changed();
+                }
+            })
+        };
+        res.is_ok()
+    }
+}
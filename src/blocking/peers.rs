@@ -0,0 +1,187 @@
+//! Accessing one node of a PinList together with the others
+
+use core::{
+    pin::Pin,
+    ptr::{NonNull, addr_of_mut},
+};
+
+use mutex::ScopedRawMutex;
+
+use super::{
+    list::PinList,
+    node::{NodeHandle, NodeHeader},
+};
+
+/// An [`Iterator`] over `Pin<&mut T>` items of all nodes of a [`PinList`]
+/// except one
+///
+/// Obtained by calling [`NodeHandle::with_lock_and_peers()`].
+///
+/// [`PinList`]: crate::blocking::PinList
+pub struct IterPeers<'a, T> {
+    iter: cordyceps::list::IterRaw<'a, NodeHeader<T>>,
+    skip: NonNull<NodeHeader<T>>,
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Access the items of the nodes of `a` and `b` together, within a
+    /// closure.
+    ///
+    /// Returns `None` if either node is not attached to this list. The mutex
+    /// is locked for the duration of the closure.
+    ///
+    /// Panics if `a` and `b` refer to the same node.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // Credits held by each peer
+    /// static PEERS: PinList<CsRm, u32> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&PEERS, 10));
+    /// let node_b = pin!(Node::new_for(&PEERS, 0));
+    /// let hdl_a = node_a.attach();
+    /// let hdl_b = node_b.attach();
+    ///
+    /// // Transfer credits atomically
+    /// PEERS.with_two(&hdl_a, &hdl_b, |a, b| {
+    ///     let (a, b) = (a.get_mut(), b.get_mut());
+    ///     *b += 4;
+    ///     *a -= 4;
+    /// });
+    /// let items = PEERS.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[6, 4], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_two<U, F>(
+        &self,
+        a: &NodeHandle<'_, '_, R, T>,
+        b: &NodeHandle<'_, '_, R, T>,
+        f: F,
+    ) -> Option<U>
+    where
+        F: for<'a> FnOnce(Pin<&'a mut T>, Pin<&'a mut T>) -> U,
+    {
+        assert_ne!(
+            a.hdr(),
+            b.hdr(),
+            "`with_two()` called with two handles to the same node"
+        );
+        self.inner.with_lock(|_inner| {
+            if !self.is_linked_here(a) || !self.is_linked_here(b) {
+                return None;
+            }
+            let (ha, hb) = (a.hdr(), b.hdr());
+            // SAFETY: We hold the lock of the list both nodes are linked into, and
+            // they are distinct nodes, so these are the only references to their
+            // items. We are providing Pin<&mut T>s, preventing them from being
+            // moved out.
+            let (ta, tb) = unsafe {
+                (
+                    Pin::new_unchecked(&mut *addr_of_mut!((*ha.as_ptr()).t)),
+                    Pin::new_unchecked(&mut *addr_of_mut!((*hb.as_ptr()).t)),
+                )
+            };
+            Some(f(ta, tb))
+        })
+    }
+}
+
+// ---- impl NodeHandle ----
+
+impl<R: ScopedRawMutex, T> NodeHandle<'_, '_, R, T> {
+    /// Access the item of this node, and iterate over the items of all other
+    /// nodes of the list, within a closure.
+    ///
+    /// The mutex is locked for the duration of the closure. If the node is
+    /// not attached, all nodes of the list it was created for are visited.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // (priority, is_leader)
+    /// static TASKS: PinList<CsRm, (u8, bool)> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&TASKS, (3, false)));
+    /// let node_b = pin!(Node::new_for(&TASKS, (5, true)));
+    /// let node_c = pin!(Node::new_for(&TASKS, (9, false)));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let hdl_c = node_c.attach();
+    ///
+    /// // C claims leadership, if it outranks every other node
+    /// hdl_c.with_lock_and_peers(|mut me, peers| {
+    ///     let peers: Vec<_> = peers.map(|p| p.get_mut()).collect();
+    ///     if peers.iter().all(|p| p.0 < me.0) {
+    ///         peers.into_iter().for_each(|p| p.1 = false);
+    ///         me.1 = true;
+    ///     }
+    /// });
+    ///
+    /// let leaders = TASKS.with_iter(|n| n.filter(|t| t.1).count());
+    /// assert_eq!(1, leaders);
+    /// assert!(hdl_c.with_lock(|t| t.1));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_lock_and_peers<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(Pin<&'a mut T>, IterPeers<'a, T>) -> U,
+    {
+        let hdr = self.hdr();
+        self.with_inner(|inner| {
+            // SAFETY: We hold the lock, and the peers iterator never yields this
+            // node, so this is the only reference to its item. We are providing
+            // a Pin<&mut T>, preventing the item from being moved out.
+            let this = unsafe { Pin::new_unchecked(&mut *addr_of_mut!((*hdr.as_ptr()).t)) };
+            f(
+                this,
+                IterPeers {
+                    iter: inner.list.iter_raw(),
+                    skip: hdr,
+                },
+            )
+        })
+    }
+}
+
+// ---- impl IterPeers ----
+
+impl<'a, T> Iterator for IterPeers<'a, T> {
+    type Item = Pin<&'a mut T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hdr = self.iter.find(|hdr| *hdr != self.skip)?;
+        // SAFETY: The list is locked for 'a, each node is yielded at most once,
+        // and the skipped node is never yielded, so this is the only reference
+        // to the item. Nodes are pinned, and we only provide a Pin<&mut T>.
+        Some(unsafe { Pin::new_unchecked(&mut *addr_of_mut!((*hdr.as_ptr()).t)) })
+    }
+}
+
+impl<T> DoubleEndedIterator for IterPeers<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let hdr = self.iter.rfind(|hdr| *hdr != self.skip)?;
+        // SAFETY: As in `next()`
+        Some(unsafe { Pin::new_unchecked(&mut *addr_of_mut!((*hdr.as_ptr()).t)) })
+    }
+}
@@ -0,0 +1,213 @@
+//! Raw mutex adapters and type aliases for a PinList
+
+#[cfg(feature = "chaos")]
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use mutex::raw_impls::cs::CriticalSectionRawMutex;
+#[cfg(any(feature = "lock_api", feature = "chaos"))]
+use mutex::{ConstInit, ScopedRawMutex};
+
+use super::list::PinList;
+
+/// A [`PinList`] using a [`CriticalSectionRawMutex`]
+pub type CsPinList<T> = PinList<CriticalSectionRawMutex, T>;
+
+/// An adapter allowing any [`lock_api::RawMutex`] to be used as the mutex
+/// of a [`PinList`]
+///
+/// Requires the `lock_api` feature.
+#[cfg(feature = "lock_api")]
+pub struct LockApiRawMutex<M>(M);
+
+/// A [`parking_lot::RawMutex`], usable as the mutex of a [`PinList`]
+///
+/// Requires the `parking_lot` feature.
+#[cfg(feature = "parking_lot")]
+pub type ParkingLotRawMutex = LockApiRawMutex<parking_lot::RawMutex>;
+
+/// A [`PinList`] using a [`ParkingLotRawMutex`]
+///
+/// Requires the `parking_lot` feature.
+#[cfg(feature = "parking_lot")]
+pub type ParkingLotPinList<T> = PinList<ParkingLotRawMutex, T>;
+
+/// A wrapper around another raw mutex, which injects random delays and yield
+/// points around lock acquisition and release
+///
+/// This is intended for soak testing on std, to flush out ordering assumptions
+/// that only hold by accident of timing. It should not be used in production.
+///
+/// Requires the `chaos` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(all(feature = "_docs", feature = "chaos"))]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{ChaosRawMutex, Node, PinList};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<ChaosRawMutex<CsRm>, u64> = PinList::new();
+///
+/// let node_a = pin!(Node::new_for(&LIST, 123));
+/// let _hdl_a = node_a.attach();
+/// LIST.with_iter(|mut i| assert_eq!(Some(&123), i.next()));
+/// # }
+/// # #[cfg(all(feature = "_docs", feature = "chaos"))]
+/// # example()
+/// ```
+#[cfg(feature = "chaos")]
+pub struct ChaosRawMutex<M> {
+    inner: M,
+    rng: AtomicU32,
+}
+
+// ---- impl LockApiRawMutex ----
+
+#[cfg(feature = "lock_api")]
+impl<M: lock_api::RawMutex> LockApiRawMutex<M> {
+    /// Create a new, unlocked, [`LockApiRawMutex`].
+    pub const fn new() -> Self {
+        Self(M::INIT)
+    }
+}
+
+#[cfg(feature = "lock_api")]
+impl<M: lock_api::RawMutex> Default for LockApiRawMutex<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "lock_api")]
+impl<M: lock_api::RawMutex> ConstInit for LockApiRawMutex<M> {
+    const INIT: Self = Self::new();
+}
+
+/// Unlocks the inner mutex when dropped, including when unwinding.
+#[cfg(feature = "lock_api")]
+struct Unlock<'a, M: lock_api::RawMutex>(&'a M);
+
+#[cfg(feature = "lock_api")]
+impl<M: lock_api::RawMutex> Drop for Unlock<'_, M> {
+    fn drop(&mut self) {
+        // SAFETY: An `Unlock` is only created after the mutex has been locked
+        unsafe { self.0.unlock() }
+    }
+}
+
+// SAFETY: The inner mutex is locked for the duration of each closure, and
+// is unlocked when the closure returns or unwinds.
+#[cfg(feature = "lock_api")]
+unsafe impl<M: lock_api::RawMutex> ScopedRawMutex for LockApiRawMutex<M> {
+    fn try_with_lock<R>(&self, f: impl FnOnce() -> R) -> Option<R> {
+        if !self.0.try_lock() {
+            return None;
+        }
+        let _unlock = Unlock(&self.0);
+        Some(f())
+    }
+
+    fn with_lock<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.0.lock();
+        let _unlock = Unlock(&self.0);
+        f()
+    }
+
+    fn is_locked(&self) -> bool {
+        self.0.is_locked()
+    }
+}
+
+// ---- impl ChaosRawMutex ----
+
+#[cfg(feature = "chaos")]
+impl<M> ChaosRawMutex<M> {
+    /// The longest delay injected, in microseconds
+    const MAX_DELAY_US: u32 = 64;
+
+    /// Wrap the given mutex, seeding the chaos with `seed`.
+    ///
+    /// Using the same seed gives the same sequence of decisions per mutex,
+    /// though the resulting interleaving still depends on the OS scheduler.
+    pub const fn with_seed(inner: M, seed: u32) -> Self {
+        Self {
+            inner,
+            // xorshift gets stuck at zero
+            rng: AtomicU32::new(seed | 1),
+        }
+    }
+
+    /// Maybe yield or sleep for a short random time.
+    fn chaos(&self) {
+        // Racing updates of the state just add more chaos
+        let mut x = self.rng.load(Ordering::Relaxed);
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng.store(x, Ordering::Relaxed);
+
+        match x % 4 {
+            0 => {}
+            1 => std::thread::yield_now(),
+            _ => std::thread::sleep(std::time::Duration::from_micros(u64::from(
+                (x >> 8) % Self::MAX_DELAY_US,
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "chaos")]
+impl<M: ConstInit> ChaosRawMutex<M> {
+    /// Create a new, unlocked, [`ChaosRawMutex`] with a fixed seed.
+    pub const fn new() -> Self {
+        Self::with_seed(M::INIT, 0x5eed_1234)
+    }
+}
+
+#[cfg(feature = "chaos")]
+impl<M: ConstInit> Default for ChaosRawMutex<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "chaos")]
+impl<M: ConstInit> ConstInit for ChaosRawMutex<M> {
+    const INIT: Self = Self::new();
+}
+
+// SAFETY: All locking is delegated to the inner mutex, chaos is only
+// injected around the calls.
+#[cfg(feature = "chaos")]
+unsafe impl<M: ScopedRawMutex> ScopedRawMutex for ChaosRawMutex<M> {
+    fn try_with_lock<R>(&self, f: impl FnOnce() -> R) -> Option<R> {
+        self.chaos();
+        let res = self.inner.try_with_lock(|| {
+            self.chaos();
+            let r = f();
+            self.chaos();
+            r
+        });
+        self.chaos();
+        res
+    }
+
+    fn with_lock<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.chaos();
+        let res = self.inner.with_lock(|| {
+            self.chaos();
+            let r = f();
+            self.chaos();
+            r
+        });
+        self.chaos();
+        res
+    }
+
+    fn is_locked(&self) -> bool {
+        self.inner.is_locked()
+    }
+}
@@ -0,0 +1,190 @@
+//! Sentinel nodes partitioning a PinList into named regions
+
+use core::pin::Pin;
+
+use mutex::ScopedRawMutex;
+use pin_project::pin_project;
+
+use super::{
+    list::{Iter, IterPinMut, PinList},
+    node::{Node, NodeHandle},
+};
+
+/// An entry of a [`PinList`] that is partitioned into named regions
+///
+/// A [`RegionEntry::Sentinel`] marks the start of a region, which contains all
+/// [`RegionEntry::Item`]s up to the next sentinel (or the end of the list).
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{Node, PinList, RegionEntry};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<CsRm, RegionEntry<u64>> = PinList::new();
+///
+/// // Attach the sentinels, in order
+/// let high = pin!(Node::new_for(&LIST, RegionEntry::Sentinel("high")));
+/// let low = pin!(Node::new_for(&LIST, RegionEntry::Sentinel("low")));
+/// let _hdl_high = high.attach();
+/// let _hdl_low = low.attach();
+///
+/// // Items can be attached to the end of a specific region
+/// let node_a = pin!(Node::new_for(&LIST, RegionEntry::Item(123)));
+/// let node_b = pin!(Node::new_for(&LIST, RegionEntry::Item(456)));
+/// let Ok(_hdl_a) = node_a.attach_in_region("low") else { panic!() };
+/// let Ok(_hdl_b) = node_b.attach_in_region("high") else { panic!() };
+///
+/// let high = LIST.with_region_iter("high", |n| n.copied().collect::<Vec<_>>());
+/// assert_eq!(Some(vec![456]), high);
+/// let low = LIST.with_region_iter("low", |n| n.copied().collect::<Vec<_>>());
+/// assert_eq!(Some(vec![123]), low);
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+#[pin_project(project = RegionEntryProj)]
+pub enum RegionEntry<T> {
+    /// Marks the start of the named region
+    Sentinel(&'static str),
+    /// An item within a region
+    Item(#[pin] T),
+}
+
+/// An [`Iterator`] over `&T` items of one region of a [`PinList`]
+///
+/// Obtained by calling [`PinList::with_region_iter()`].
+pub struct RegionIter<'a, T> {
+    iter: Iter<'a, RegionEntry<T>>,
+    done: bool,
+}
+
+/// An [`Iterator`] over `Pin<&mut T>` items of one region of a [`PinList`]
+///
+/// Obtained by calling [`PinList::with_region_iter_pin_mut()`].
+pub struct RegionIterPinMut<'a, T> {
+    iter: IterPinMut<'a, RegionEntry<T>>,
+    done: bool,
+}
+
+// ---- impl RegionEntry ----
+
+impl<T> RegionEntry<T> {
+    /// Is this the sentinel of the region `name`?
+    fn is_sentinel_for(&self, name: &str) -> bool {
+        matches!(self, RegionEntry::Sentinel(n) if *n == name)
+    }
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, RegionEntry<T>> {
+    /// Call the given closure with a [`RegionIter`] which iterates over the
+    /// `&T`s in the region `name`.
+    ///
+    /// Returns `None` if no sentinel for the region is attached.
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_region_iter<U, F>(&self, name: &str, f: F) -> Option<U>
+    where
+        F: for<'a> FnOnce(RegionIter<'a, T>) -> U,
+    {
+        self.with_iter(|mut iter| {
+            iter.find(|e| e.is_sentinel_for(name))?;
+            Some(f(RegionIter { iter, done: false }))
+        })
+    }
+
+    /// Call the given closure with a [`RegionIterPinMut`] which iterates over
+    /// the `Pin<&mut T>`s in the region `name`.
+    ///
+    /// Returns `None` if no sentinel for the region is attached.
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_region_iter_pin_mut<U, F>(&self, name: &str, f: F) -> Option<U>
+    where
+        F: for<'a> FnOnce(RegionIterPinMut<'a, T>) -> U,
+    {
+        self.with_iter_pin_mut(|mut iter| {
+            iter.find(|e| e.is_sentinel_for(name))?;
+            Some(f(RegionIterPinMut { iter, done: false }))
+        })
+    }
+}
+
+// ---- impl Node ----
+
+impl<'list, R: ScopedRawMutex, T> Node<'list, R, RegionEntry<T>> {
+    /// Attach the given node at the end of the region `name`.
+    ///
+    /// If no sentinel for the region is attached, the node is NOT attached,
+    /// and is returned back. Otherwise this behaves like [`Node::attach()`].
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn attach_in_region<'node>(
+        self: Pin<&'node mut Self>,
+        name: &str,
+    ) -> Result<NodeHandle<'list, 'node, R, RegionEntry<T>>, Pin<&'node mut Self>> {
+        self.attach_with(|list, hdr| {
+            let mut cursor = list.cursor_front_mut();
+            loop {
+                match cursor.current() {
+                    None => return false,
+                    Some(cur) if cur.t.is_sentinel_for(name) => break,
+                    Some(_) => cursor.move_next(),
+                }
+            }
+            cursor.move_next();
+            while let Some(cur) = cursor.current() {
+                if matches!(cur.t, RegionEntry::Sentinel(_)) {
+                    break;
+                }
+                cursor.move_next();
+            }
+            // If we reached the end of the list, this inserts at the back
+            cursor.insert_before(hdr);
+            true
+        })
+    }
+}
+
+// ---- impl RegionIter ----
+
+impl<'a, T> Iterator for RegionIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.iter.next() {
+            Some(RegionEntry::Item(t)) => Some(t),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+// ---- impl RegionIterPinMut ----
+
+impl<'a, T> Iterator for RegionIterPinMut<'a, T> {
+    type Item = Pin<&'a mut T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.iter.next().map(|e| e.project()) {
+            Some(RegionEntryProj::Item(t)) => Some(t),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,545 @@
+//! Operations relative to attached nodes of a PinList
+
+use core::{
+    iter::Rev,
+    mem,
+    pin::Pin,
+    ptr::{self, NonNull, addr_of},
+    sync::atomic::Ordering,
+};
+
+use cordyceps::{List, list::Links};
+use mutex::ScopedRawMutex;
+
+use super::{
+    list::{Iter, PinList},
+    node::{Node, NodeHandle, NodeHeader},
+};
+
+/// The position of `hdr` in `list`, if it is linked into it.
+fn position_of<T>(list: &List<NodeHeader<T>>, hdr: NonNull<NodeHeader<T>>) -> Option<usize> {
+    list.iter().position(|h| ptr::eq(h, hdr.as_ptr()))
+}
+
+/// A link to a node, as stored in [`Links`]
+type Link<T> = Option<NonNull<NodeHeader<T>>>;
+
+/// The nodes directly before and after `hdr`, read from its own links.
+///
+/// `cordyceps` does not expose the links of a node, but [`Links`] holds
+/// exactly its `next` and `prev` pointers, in that order, so they are read
+/// directly instead of searching the list for `hdr`.
+///
+/// # Safety
+///
+/// `hdr` must be live and linked into `list`, and the lock of `list` must be
+/// held.
+unsafe fn neighbors<T>(
+    list: &List<NodeHeader<T>>,
+    hdr: NonNull<NodeHeader<T>>,
+) -> (Link<T>, Link<T>) {
+    const {
+        assert!(mem::size_of::<Links<NodeHeader<T>>>() == 2 * mem::size_of::<Link<T>>());
+    }
+    // SAFETY: `Links` is the size of two links and holds nothing else, so its
+    // memory is exactly the two pointers, and any value is a valid `Link`. We
+    // hold the lock, so the list is not modifying them.
+    let read = |hdr: NonNull<NodeHeader<T>>| unsafe {
+        addr_of!((*hdr.as_ptr()).links)
+            .cast::<[Link<T>; 2]>()
+            .read()
+    };
+    debug_assert!(
+        list.len() < 2
+            || list
+                .front()
+                .is_some_and(|f| read(NonNull::from(&*f))[1].is_none()),
+        "the links of a node are not laid out as `next`, `prev`"
+    );
+    let [next, prev] = read(hdr);
+    (prev, next)
+}
+
+/// Link `hdr` into `list` directly before or after `anchor`.
+///
+/// Returns `false` if `anchor` is not linked into `list`.
+fn insert_next_to<T>(
+    list: &mut List<NodeHeader<T>>,
+    anchor: NonNull<NodeHeader<T>>,
+    hdr: NonNull<NodeHeader<T>>,
+    after: bool,
+) -> bool {
+    let mut cursor = list.cursor_front_mut();
+    loop {
+        match cursor.current() {
+            None => return false,
+            Some(cur) if ptr::eq(cur.get_ref(), anchor.as_ptr()) => break,
+            Some(_) => cursor.move_next(),
+        }
+    }
+    if after {
+        cursor.insert_after(hdr);
+    } else {
+        cursor.insert_before(hdr);
+    }
+    true
+}
+
+/// Link `hdr` into `list` at position `at`, or at the back if `at` is the
+/// length of the list.
+fn insert_at<T>(list: &mut List<NodeHeader<T>>, at: usize, hdr: NonNull<NodeHeader<T>>) {
+    let mut cursor = list.cursor_front_mut();
+    for _ in 0..at {
+        cursor.move_next();
+    }
+    cursor.insert_before(hdr);
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Is the node of `hdl` linked into this list?
+    ///
+    /// Must be called with the mutex of this list locked to get a stable answer.
+    pub(crate) fn is_linked_here(&self, hdl: &NodeHandle<'_, '_, R, T>) -> bool {
+        // SAFETY: The handle borrows the node, so it is live, and `linked_to` is
+        // only accessed atomically
+        let linked_to = unsafe { &(*hdl.hdr().as_ptr()).linked_to };
+        ptr::eq(linked_to.load(Ordering::Acquire), self.as_erased())
+    }
+
+    /// Is the node of `hdl` currently attached to this list?
+    ///
+    /// Each node records the list it is linked into, so this does not need to
+    /// lock the list or walk its nodes. The answer may be out of date as soon
+    /// as it is returned, if another context is detaching or moving the node.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    /// static OTHER: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let hdl_a = node_a.attach();
+    ///
+    /// assert!(LIST.contains(&hdl_a));
+    /// assert!(!OTHER.contains(&hdl_a));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn contains(&self, hdl: &NodeHandle<'_, '_, R, T>) -> bool {
+        self.is_linked_here(hdl)
+    }
+
+    /// Call the given closure with an [`Iter`] which iterates over `&T`s,
+    /// starting at the node of `hdl` and continuing to the back of the list.
+    ///
+    /// Returns `None` if the node of `hdl` is not attached to this list.
+    ///
+    /// The blocking mutex is locked while the node is found, and for the
+    /// duration of the call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 1));
+    /// let node_b = pin!(Node::new_for(&LIST, 2));
+    /// let node_c = pin!(Node::new_for(&LIST, 3));
+    /// let _hdl_a = node_a.attach();
+    /// let hdl_b = node_b.attach();
+    /// let _hdl_c = node_c.attach();
+    ///
+    /// // The neighborhood of B
+    /// let after = LIST.with_iter_from(&hdl_b, |n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(Some(vec![2, 3]), after);
+    /// let before = LIST.with_iter_rev_from(&hdl_b, |n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(Some(vec![2, 1]), before);
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_iter_from<U, F>(&self, hdl: &NodeHandle<'_, '_, R, T>, f: F) -> Option<U>
+    where
+        F: for<'a> FnOnce(Iter<'a, T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            if !self.is_linked_here(hdl) {
+                return None;
+            }
+            let pos = position_of(&inner.list, hdl.hdr())?;
+            let mut iter = inner.list.iter();
+            for _ in 0..pos {
+                iter.next();
+            }
+            Some(f(Iter { iter }))
+        })
+    }
+
+    /// Call the given closure with an [`Iter`] which iterates over `&T`s,
+    /// starting at the node of `hdl` and continuing to the front of the list.
+    ///
+    /// Returns `None` if the node of `hdl` is not attached to this list.
+    ///
+    /// The blocking mutex is locked while the node is found, and for the
+    /// duration of the call to `f()`.
+    pub fn with_iter_rev_from<U, F>(&self, hdl: &NodeHandle<'_, '_, R, T>, f: F) -> Option<U>
+    where
+        F: for<'a> FnOnce(Rev<Iter<'a, T>>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            if !self.is_linked_here(hdl) {
+                return None;
+            }
+            let pos = position_of(&inner.list, hdl.hdr())?;
+            let mut iter = inner.list.iter();
+            for _ in pos + 1..inner.list.len() {
+                iter.next_back();
+            }
+            Some(f(Iter { iter }.rev()))
+        })
+    }
+
+    /// Exchange the positions of the nodes of `a` and `b` in this list.
+    ///
+    /// Returns `false`, and does nothing, if either node is not attached to
+    /// this list.
+    ///
+    /// The blocking mutex is locked while the nodes are found and relinked,
+    /// which takes time proportional to the number of nodes.
+    ///
+    /// On an ordered list, swapping unequal nodes breaks the order. See
+    /// [`PinList::new_ordered()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 1));
+    /// let node_b = pin!(Node::new_for(&LIST, 2));
+    /// let node_c = pin!(Node::new_for(&LIST, 3));
+    /// let hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let hdl_c = node_c.attach();
+    ///
+    /// assert!(LIST.swap(&hdl_a, &hdl_c));
+    ///
+    /// let items = LIST.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[3, 2, 1], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn swap(&self, a: &NodeHandle<'_, '_, R, T>, b: &NodeHandle<'_, '_, R, T>) -> bool {
+        self.inner.with_lock(|inner| {
+            if !self.is_linked_here(a) || !self.is_linked_here(b) {
+                return false;
+            }
+            let (a, b) = (a.hdr(), b.hdr());
+            if a == b {
+                return true;
+            }
+            let (Some(pos_a), Some(pos_b)) =
+                (position_of(&inner.list, a), position_of(&inner.list, b))
+            else {
+                unreachable!("nodes linked to this list are in it");
+            };
+            let ((first, i), (second, j)) = if pos_a < pos_b {
+                ((a, pos_a), (b, pos_b))
+            } else {
+                ((b, pos_b), (a, pos_a))
+            };
+            // SAFETY: Both nodes are linked into this list, and we hold its lock
+            unsafe {
+                inner.list.remove(first);
+                inner.list.remove(second);
+            }
+            insert_at(&mut inner.list, i, second);
+            insert_at(&mut inner.list, j, first);
+            inner.changed();
+            true
+        })
+    }
+}
+
+// ---- impl Node ----
+
+impl<'list, R: ScopedRawMutex, T> Node<'list, R, T> {
+    /// Attach the given node directly before the node of `anchor`.
+    ///
+    /// If the node of `anchor` is not attached to the list this node was
+    /// created for, the node is NOT attached, and is returned back. Otherwise
+    /// this behaves like [`Node::attach()`].
+    ///
+    /// The mutex will be locked while the list is searched for `anchor`. On
+    /// an ordered list, the node is attached at its sorted position instead.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static STAGES: PinList<CsRm, &'static str> = PinList::new();
+    ///
+    /// let decode = pin!(Node::new_for(&STAGES, "decode"));
+    /// let store = pin!(Node::new_for(&STAGES, "store"));
+    /// let hdl_decode = decode.attach();
+    /// let _hdl_store = store.attach();
+    ///
+    /// let validate = pin!(Node::new_for(&STAGES, "validate"));
+    /// let Ok(_hdl_validate) = validate.attach_after(&hdl_decode) else { panic!() };
+    ///
+    /// let items = STAGES.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&["decode", "validate", "store"], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn attach_before<'node>(
+        self: Pin<&'node mut Self>,
+        anchor: &NodeHandle<'_, '_, R, T>,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, Pin<&'node mut Self>> {
+        let anchor = anchor.hdr();
+        self.attach_with(|list, hdr| insert_next_to(list, anchor, hdr, false))
+    }
+
+    /// Attach the given node directly after the node of `anchor`.
+    ///
+    /// See [`Node::attach_before()`] for details.
+    #[cfg_attr(feature = "attach-location", track_caller)]
+    pub fn attach_after<'node>(
+        self: Pin<&'node mut Self>,
+        anchor: &NodeHandle<'_, '_, R, T>,
+    ) -> Result<NodeHandle<'list, 'node, R, T>, Pin<&'node mut Self>> {
+        let anchor = anchor.hdr();
+        self.attach_with(|list, hdr| insert_next_to(list, anchor, hdr, true))
+    }
+}
+
+// ---- impl NodeHandle ----
+
+impl<R: ScopedRawMutex, T> NodeHandle<'_, '_, R, T> {
+    /// The current position of this node in the list it is attached to,
+    /// counting from zero at the front, or `None` if it is not attached.
+    ///
+    /// Unlike [`NodeHandle::index()`], this changes as nodes in front of this
+    /// one are attached, detached, or reordered. The mutex is locked while
+    /// the list is walked from the front to find the node.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static QUEUE: PinList<CsRm, &str> = PinList::new();
+    ///
+    /// let node_b = pin!(Node::new_for(&QUEUE, "bob"));
+    /// let hdl_b = node_b.attach();
+    /// {
+    ///     let node_a = pin!(Node::new_for(&QUEUE, "alice"));
+    ///     let _hdl_a = node_a.attach_front();
+    ///     assert_eq!(Some(1), hdl_b.position());
+    /// }
+    /// // Alice left the queue
+    /// assert_eq!(Some(0), hdl_b.position());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn position(&self) -> Option<usize> {
+        let hdr = self.hdr();
+        self.with_inner(|inner| position_of(&inner.list, hdr))
+    }
+
+    /// Move this node to the front of the list it is attached to.
+    ///
+    /// Returns `false`, and does nothing, if the node is not attached. The
+    /// mutex is locked while the node is relinked, which takes constant time.
+    ///
+    /// On an ordered list, moving a node breaks the order. See
+    /// [`PinList::new_ordered()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // Cache entries, most recently used first
+    /// static LRU: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LRU, 1));
+    /// let node_b = pin!(Node::new_for(&LRU, 2));
+    /// let node_c = pin!(Node::new_for(&LRU, 3));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let hdl_c = node_c.attach();
+    ///
+    /// // C was used
+    /// assert!(hdl_c.move_to_front());
+    ///
+    /// let items = LRU.with_iter(|n| n.copied().collect::<Vec<_>>());
+    /// assert_eq!(&[3, 1, 2], items.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn move_to_front(&self) -> bool {
+        self.move_to_end(true)
+    }
+
+    /// Move this node to the back of the list it is attached to.
+    ///
+    /// See [`NodeHandle::move_to_front()`] for details.
+    pub fn move_to_back(&self) -> bool {
+        self.move_to_end(false)
+    }
+
+    /// Relink this node at the front (or back) of the list it is linked into.
+    fn move_to_end(&self, front: bool) -> bool {
+        let hdr = self.hdr();
+        // SAFETY: The handle borrows the node, so it is live, and the node was
+        // created for a list with the same mutex type as any it is linked into.
+        // We hold the mutex of the list it is linked into, so we can relink it.
+        let res = unsafe {
+            NodeHeader::with_linked_list::<R, _, _>(hdr, |inner| {
+                inner.list.remove(hdr);
+                if front {
+                    inner.list.push_front(hdr);
+                } else {
+                    inner.list.push_back(hdr);
+                }
+                inner.changed();
+            })
+        };
+        res.is_ok()
+    }
+
+    /// Access the item of this node, and the item of the node directly after
+    /// it, within a closure.
+    ///
+    /// The neighbor is `None` if this node is at the back of the list.
+    /// Returns `None`, without calling `f()`, if this node is not attached.
+    ///
+    /// The neighbor is read from the links of this node, so this takes the
+    /// same time wherever the node is in the list. The mutex is locked for the
+    /// duration of the closure.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// // Does this participant hold the token?
+    /// static RING: PinList<CsRm, bool> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&RING, true));
+    /// let node_b = pin!(Node::new_for(&RING, false));
+    /// let hdl_a = node_a.attach();
+    /// let hdl_b = node_b.attach();
+    ///
+    /// // Pass the token on
+    /// hdl_a.with_next(|me, next| {
+    ///     if let Some(next) = next {
+    ///         *me.get_mut() = false;
+    ///         *next.get_mut() = true;
+    ///     }
+    /// });
+    /// assert!(hdl_b.with_lock(|t| *t));
+    ///
+    /// assert_eq!(Some(false), hdl_b.with_next(|_me, next| next.is_some()));
+    /// assert_eq!(Some(true), hdl_b.with_prev(|_me, prev| prev.is_some()));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_next<U, F>(&self, f: F) -> Option<U>
+    where
+        F: for<'a> FnOnce(Pin<&'a mut T>, Option<Pin<&'a mut T>>) -> U,
+    {
+        self.with_neighbor(true, f)
+    }
+
+    /// Access the item of this node, and the item of the node directly
+    /// before it, within a closure.
+    ///
+    /// The neighbor is `None` if this node is at the front of the list. See
+    /// [`NodeHandle::with_next()`] for details.
+    pub fn with_prev<U, F>(&self, f: F) -> Option<U>
+    where
+        F: for<'a> FnOnce(Pin<&'a mut T>, Option<Pin<&'a mut T>>) -> U,
+    {
+        self.with_neighbor(false, f)
+    }
+
+    /// Access this node and its next (or previous) neighbor.
+    fn with_neighbor<U, F>(&self, next: bool, f: F) -> Option<U>
+    where
+        F: for<'a> FnOnce(Pin<&'a mut T>, Option<Pin<&'a mut T>>) -> U,
+    {
+        let hdr = self.hdr();
+        // SAFETY: The handle borrows the node, so it is live, and the node was
+        // created for a list with the same mutex type as any it is linked into.
+        let res = unsafe {
+            NodeHeader::with_linked_list::<R, _, _>(hdr, |inner| {
+                let (prev, after) = neighbors(&inner.list, hdr);
+                let neighbor = if next { after } else { prev };
+                // SAFETY: We hold the lock of the list both nodes are linked into,
+                // and they are distinct nodes, so these are the only references to
+                // their items. We are providing Pin<&mut T>s, preventing them from
+                // being moved out.
+                let (this, neighbor) = (
+                    NodeHeader::item_pin_mut(hdr),
+                    neighbor.map(|n| NodeHeader::item_pin_mut(n)),
+                );
+                f(this, neighbor)
+            })
+        };
+        res.ok()
+    }
+}
@@ -0,0 +1,185 @@
+//! Round-robin iteration over a PinList
+
+use core::pin::Pin;
+
+use cordyceps::List;
+use mutex::ScopedRawMutex;
+
+use super::{
+    id::NodeId,
+    list::{PinList, PinListInner},
+    node::NodeHeader,
+};
+
+/// An [`Iterator`] over `&T` nodes of a [`PinList`], starting after the node
+/// visited last
+///
+/// Obtained by calling [`PinList::with_iter_round_robin()`].
+pub struct IterRoundRobin<'a, T> {
+    iter: cordyceps::list::Iter<'a, NodeHeader<T>>,
+    last: &'a mut Option<NodeId>,
+}
+
+/// An [`Iterator`] over `Pin<&mut T>` nodes of a [`PinList`], starting after
+/// the node visited last
+///
+/// Obtained by calling [`PinList::with_iter_pin_mut_round_robin()`].
+pub struct IterRoundRobinPinMut<'a, T> {
+    iter: cordyceps::list::IterRaw<'a, NodeHeader<T>>,
+    last: &'a mut Option<NodeId>,
+}
+
+// ---- impl PinListInner ----
+
+impl<T> PinListInner<T> {
+    /// Rotate the list so that it starts after the node visited last by a
+    /// round-robin iterator, and call `f`, then undo the rotation.
+    ///
+    /// The rotation is undone even if `f` panics.
+    fn with_rotated<U, F>(&mut self, f: F) -> U
+    where
+        F: FnOnce(&mut List<NodeHeader<T>>, &mut Option<NodeId>) -> U,
+    {
+        let len = self.list.len();
+        let start = match self.rr_last {
+            Some(last) => self
+                .list
+                .iter()
+                .position(|hdr| NodeId::of(hdr) == last)
+                .map_or(0, |pos| (pos + 1) % len),
+            None => 0,
+        };
+        rotate_left(&mut self.list, start);
+        let guard = Unrotate {
+            list: &mut self.list,
+            n: (len - start) % len.max(1),
+        };
+        f(guard.list, &mut self.rr_last)
+    }
+}
+
+/// Rotates a list back when dropped, see [`PinListInner::with_rotated()`].
+struct Unrotate<'a, T> {
+    list: &'a mut List<NodeHeader<T>>,
+    n: usize,
+}
+
+impl<T> Drop for Unrotate<'_, T> {
+    fn drop(&mut self) {
+        rotate_left(self.list, self.n);
+    }
+}
+
+/// Move the first `n` nodes of `list` to the back.
+fn rotate_left<T>(list: &mut List<NodeHeader<T>>, n: usize) {
+    if n == 0 {
+        return;
+    }
+    let mut rotated = list.split_off(n);
+    rotated.append(list);
+    *list = rotated;
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Call the given closure with an [`IterRoundRobin`] which iterates over
+    /// `&T`s, starting after the last node yielded by the previous round-robin
+    /// iteration, and wrapping around to the front of the list.
+    ///
+    /// This allows fairly sharing work across nodes, when each call only
+    /// visits some of them. If the last node yielded has since been detached,
+    /// iteration starts from the front of the list.
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static CLIENTS: PinList<CsRm, &'static str> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&CLIENTS, "a"));
+    /// let node_b = pin!(Node::new_for(&CLIENTS, "b"));
+    /// let node_c = pin!(Node::new_for(&CLIENTS, "c"));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    /// let _hdl_c = node_c.attach();
+    ///
+    /// // Serve two clients at a time
+    /// let served = CLIENTS.with_iter_round_robin(|n| n.take(2).copied().collect::<Vec<_>>());
+    /// assert_eq!(&["a", "b"], served.as_slice());
+    /// let served = CLIENTS.with_iter_round_robin(|n| n.take(2).copied().collect::<Vec<_>>());
+    /// assert_eq!(&["c", "a"], served.as_slice());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_iter_round_robin<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(IterRoundRobin<'a, T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            inner.with_rotated(|list, last| {
+                f(IterRoundRobin {
+                    iter: list.iter(),
+                    last,
+                })
+            })
+        })
+    }
+
+    /// Call the given closure with an [`IterRoundRobinPinMut`] which iterates
+    /// over `Pin<&mut T>`s, starting after the last node yielded by the
+    /// previous round-robin iteration, and wrapping around to the front of
+    /// the list.
+    ///
+    /// See [`PinList::with_iter_round_robin()`] for details.
+    pub fn with_iter_pin_mut_round_robin<U, F>(&self, f: F) -> U
+    where
+        F: for<'a> FnOnce(IterRoundRobinPinMut<'a, T>) -> U,
+    {
+        self.inner.with_lock(|inner| {
+            inner.with_rotated(|list, last| {
+                f(IterRoundRobinPinMut {
+                    iter: list.iter_raw(),
+                    last,
+                })
+            })
+        })
+    }
+}
+
+// ---- impl IterRoundRobin ----
+
+impl<'a, T> Iterator for IterRoundRobin<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hdr = self.iter.next()?;
+        *self.last = Some(NodeId::of(hdr));
+        Some(&hdr.t)
+    }
+}
+
+// ---- impl IterRoundRobinPinMut ----
+
+impl<'a, T> Iterator for IterRoundRobinPinMut<'a, T> {
+    type Item = Pin<&'a mut T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hdr = self.iter.next()?;
+        // SAFETY: The list is locked for 'a, and each node is yielded at most
+        // once, so this is the only reference to the item.
+        unsafe {
+            *self.last = Some(NodeId::of(hdr.as_ref()));
+            Some(NodeHeader::item_pin_mut(hdr))
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Reference-counted handles to attached nodes
+
+use alloc::sync::Arc;
+use core::ops::Deref;
+
+use mutex::ScopedRawMutex;
+
+use super::node::NodeHandle;
+
+/// A reference-counted [`NodeHandle`], which can be cloned and shared between
+/// tasks
+///
+/// All clones refer to the same node, and access its item through the mutex
+/// of the list, like a [`NodeHandle`]. The node is still only removed from
+/// the list when it is dropped, which cannot happen while any clone exists.
+///
+/// Obtained by calling [`NodeHandle::into_shared()`]. Requires the `alloc`
+/// feature.
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(all(feature = "_docs", feature = "alloc"))]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{PinList, Node};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<CsRm, u64> = PinList::new();
+///
+/// let node_a = pin!(Node::new_for(&LIST, 0));
+/// let hdl_a = node_a.attach().into_shared();
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         let hdl = hdl_a.clone();
+///         s.spawn(move || hdl.with_lock_mut(|t| *t += 1));
+///     }
+/// });
+/// assert_eq!(4, hdl_a.with_lock(|t| *t));
+/// # }
+/// # #[cfg(all(feature = "_docs", feature = "alloc"))]
+/// # example()
+/// ```
+pub struct ArcNodeHandle<'list, 'node, R: ScopedRawMutex, T> {
+    hdl: Arc<NodeHandle<'list, 'node, R, T>>,
+}
+
+// ---- impl NodeHandle ----
+
+impl<'list, 'node, R: ScopedRawMutex, T> NodeHandle<'list, 'node, R, T> {
+    /// Convert this handle into an [`ArcNodeHandle`], which can be cloned.
+    ///
+    /// Requires the `alloc` feature.
+    pub fn into_shared(self) -> ArcNodeHandle<'list, 'node, R, T> {
+        ArcNodeHandle {
+            hdl: Arc::new(self),
+        }
+    }
+}
+
+// ---- impl ArcNodeHandle ----
+
+impl<'list, 'node, R: ScopedRawMutex, T> ArcNodeHandle<'list, 'node, R, T> {
+    /// Recover the [`NodeHandle`], if this is the only clone.
+    ///
+    /// Otherwise, the handle is returned back.
+    pub fn try_unwrap(self) -> Result<NodeHandle<'list, 'node, R, T>, Self> {
+        Arc::try_unwrap(self.hdl).map_err(|hdl| Self { hdl })
+    }
+}
+
+impl<'list, 'node, R: ScopedRawMutex, T> Deref for ArcNodeHandle<'list, 'node, R, T> {
+    type Target = NodeHandle<'list, 'node, R, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.hdl
+    }
+}
+
+impl<R: ScopedRawMutex, T> Clone for ArcNodeHandle<'_, '_, R, T> {
+    fn clone(&self) -> Self {
+        Self {
+            hdl: self.hdl.clone(),
+        }
+    }
+}
+
+// SAFETY: All access to the item of the node is mediated by the mutex of the
+// list, as for PinList, and the rest of the handle is either immutable while
+// it exists, or only accessed atomically. Clones hold references to the mutex,
+// which may be used from another thread, so it must be Sync. If the item is
+// Send and the mutex is Sync, it is safe to implement Send for ArcNodeHandle.
+unsafe impl<R: ScopedRawMutex + Sync, T: Send> Send for ArcNodeHandle<'_, '_, R, T> {}
+
+// SAFETY: See above. If the item is Send and the mutex is Sync, it is safe to
+// implement Sync for ArcNodeHandle.
+unsafe impl<R: ScopedRawMutex + Sync, T: Send> Sync for ArcNodeHandle<'_, '_, R, T> {}
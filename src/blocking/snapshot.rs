@@ -0,0 +1,252 @@
+//! Compact binary snapshots of a PinList
+//!
+//! Snapshots are intended to be shipped to a host (e.g. over RTT or a serial
+//! port) for inspection, without requiring any serialization framework on
+//! the target.
+//!
+//! ## Format
+//!
+//! All integers are encoded as unsigned LEB128 varints.
+//!
+//! - A flags byte: bit 0 is set if payloads are included
+//! - The number of nodes
+//! - For each node, in list order:
+//!     - The node's token (its address)
+//!     - If payloads are included, the length of the payload, followed by
+//!       the payload bytes
+
+use mutex::ScopedRawMutex;
+
+use super::list::PinList;
+
+/// Set in the flags byte if the snapshot contains payloads
+const FLAG_PAYLOADS: u8 = 0b0000_0001;
+
+/// An error encoding or decoding a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The buffer is too small to hold the snapshot
+    BufferTooSmall,
+    /// The payload serializer failed
+    Payload,
+    /// The snapshot could not be decoded
+    Malformed,
+}
+
+/// A decoded snapshot of a [`PinList`]
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The nodes of the list, in order
+    pub entries: std::vec::Vec<SnapshotEntry>,
+}
+
+/// A single node of a decoded [`Snapshot`]
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    /// The token identifying the node
+    pub token: u64,
+    /// The serialized payload, if payloads were included
+    pub payload: Option<std::vec::Vec<u8>>,
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Encode a snapshot of the tokens of all nodes into `buf`.
+    ///
+    /// Returns the number of bytes used. The blocking mutex is locked for the
+    /// duration of the encoding.
+    pub fn encode_snapshot(&self, buf: &mut [u8]) -> Result<usize, SnapshotError> {
+        self.encode(buf, None::<fn(&T, &mut [u8]) -> Option<usize>>)
+    }
+
+    /// Encode a snapshot of the tokens and payloads of all nodes into `buf`.
+    ///
+    /// `ser` is called with each item and the remaining space in the buffer,
+    /// and returns the number of bytes it wrote, or `None` if it failed.
+    /// The payload is written in place, and shifted up to make room for its
+    /// length prefix, so `buf` only needs to fit the final encoding.
+    ///
+    /// Returns the number of bytes used. The blocking mutex is locked for the
+    /// duration of the encoding.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl,
+    /// # // and `std` for the decoder
+    /// # #[cfg(all(feature = "_docs", feature = "std"))]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node, decode_snapshot};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u16> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 0x1234));
+    /// let _hdl_a = node_a.attach();
+    ///
+    /// let mut buf = [0u8; 32];
+    /// let used = LIST
+    ///     .encode_snapshot_with(&mut buf, |t, out| {
+    ///         out.get_mut(..2)?.copy_from_slice(&t.to_le_bytes());
+    ///         Some(2)
+    ///     })
+    ///     .unwrap();
+    ///
+    /// // On the host
+    /// let snapshot = decode_snapshot(&buf[..used]).unwrap();
+    /// assert_eq!(1, snapshot.entries.len());
+    /// assert_eq!(Some(&[0x34, 0x12][..]), snapshot.entries[0].payload.as_deref());
+    /// # }
+    /// # #[cfg(all(feature = "_docs", feature = "std"))]
+    /// # example()
+    /// ```
+    pub fn encode_snapshot_with<F>(&self, buf: &mut [u8], ser: F) -> Result<usize, SnapshotError>
+    where
+        F: FnMut(&T, &mut [u8]) -> Option<usize>,
+    {
+        self.encode(buf, Some(ser))
+    }
+
+    fn encode<F>(&self, buf: &mut [u8], mut ser: Option<F>) -> Result<usize, SnapshotError>
+    where
+        F: FnMut(&T, &mut [u8]) -> Option<usize>,
+    {
+        self.inner.with_lock(|inner| {
+            let mut w = Writer { buf, pos: 0 };
+            w.byte(if ser.is_some() { FLAG_PAYLOADS } else { 0 })?;
+            w.varint(inner.list.len() as u64)?;
+            for hdr in inner.list.iter() {
+                w.varint(core::ptr::from_ref(hdr) as usize as u64)?;
+                if let Some(ser) = ser.as_mut() {
+                    // Write the payload in place, then shift it up by the size of
+                    // its length prefix once the length is known.
+                    let start = w.pos;
+                    let scratch = w
+                        .buf
+                        .get_mut(start..)
+                        .ok_or(SnapshotError::BufferTooSmall)?;
+                    let len = ser(&hdr.t, scratch).ok_or(SnapshotError::Payload)?;
+                    if len > scratch.len() {
+                        return Err(SnapshotError::Payload);
+                    }
+                    let prefix = varint_len(len as u64);
+                    if start + prefix + len > w.buf.len() {
+                        return Err(SnapshotError::BufferTooSmall);
+                    }
+                    w.buf.copy_within(start..start + len, start + prefix);
+                    w.varint(len as u64)?;
+                    w.pos += len;
+                }
+            }
+            Ok(w.pos)
+        })
+    }
+}
+
+/// Decode a snapshot created by [`PinList::encode_snapshot()`] or
+/// [`PinList::encode_snapshot_with()`].
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn decode_snapshot(bytes: &[u8]) -> Result<Snapshot, SnapshotError> {
+    let mut r = Reader { bytes, pos: 0 };
+    let flags = r.byte()?;
+    let count = r.varint()?;
+    let mut entries = std::vec::Vec::new();
+    for _ in 0..count {
+        let token = r.varint()?;
+        let payload = if flags & FLAG_PAYLOADS != 0 {
+            let len = usize::try_from(r.varint()?).map_err(|_| SnapshotError::Malformed)?;
+            Some(r.take(len)?.to_vec())
+        } else {
+            None
+        };
+        entries.push(SnapshotEntry { token, payload });
+    }
+    Ok(Snapshot { entries })
+}
+
+// ---- varint helpers ----
+
+/// The encoded size of `v` as a varint
+fn varint_len(mut v: u64) -> usize {
+    let mut len = 1;
+    while v >= 0x80 {
+        v >>= 7;
+        len += 1;
+    }
+    len
+}
+
+struct Writer<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn byte(&mut self, b: u8) -> Result<(), SnapshotError> {
+        let slot = self
+            .buf
+            .get_mut(self.pos)
+            .ok_or(SnapshotError::BufferTooSmall)?;
+        *slot = b;
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn varint(&mut self, mut v: u64) -> Result<(), SnapshotError> {
+        loop {
+            let b = (v & 0x7F) as u8;
+            v >>= 7;
+            if v == 0 {
+                return self.byte(b);
+            }
+            self.byte(b | 0x80)?;
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+#[cfg(feature = "std")]
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, SnapshotError> {
+        let b = *self.bytes.get(self.pos).ok_or(SnapshotError::Malformed)?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn varint(&mut self) -> Result<u64, SnapshotError> {
+        let mut v = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            v |= u64::from(b & 0x7F) << shift;
+            if b & 0x80 == 0 {
+                return Ok(v);
+            }
+        }
+        Err(SnapshotError::Malformed)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        let end = self.pos.checked_add(len).ok_or(SnapshotError::Malformed)?;
+        let out = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or(SnapshotError::Malformed)?;
+        self.pos = end;
+        Ok(out)
+    }
+}
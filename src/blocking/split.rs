@@ -0,0 +1,168 @@
+//! Separate read and write halves of a handle to an attached node
+
+use core::pin::Pin;
+
+use mutex::ScopedRawMutex;
+
+use super::{id::NodeId, node::NodeHandle};
+
+/// The read half of a [`NodeHandle`], which can only access the item
+/// immutably
+///
+/// Obtained by calling [`NodeHandle::split()`].
+pub struct ReadHandle<'list, 'node, R: ScopedRawMutex, T> {
+    hdl: NodeHandle<'list, 'node, R, T>,
+}
+
+/// The write half of a [`NodeHandle`], which can access the item mutably
+///
+/// Obtained by calling [`NodeHandle::split()`].
+pub struct WriteHandle<'list, 'node, R: ScopedRawMutex, T> {
+    hdl: NodeHandle<'list, 'node, R, T>,
+}
+
+// ---- impl NodeHandle ----
+
+impl<'list, 'node, R: ScopedRawMutex, T> NodeHandle<'list, 'node, R, T> {
+    /// Split this handle into a [`ReadHandle`] and a [`WriteHandle`].
+    ///
+    /// Both halves access the item through the mutex of the list, like the
+    /// handle itself, and can be sent to different tasks if the item is
+    /// `Send` and the mutex is `Sync`. They can be joined again with
+    /// [`WriteHandle::unsplit()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 0));
+    /// let (read, write) = node_a.attach().split();
+    ///
+    /// std::thread::scope(|s| {
+    ///     s.spawn(|| write.with_lock_mut(|t| *t = 123));
+    /// });
+    /// assert_eq!(123, read.with_lock(|t| *t));
+    ///
+    /// let hdl_a = write.unsplit(read);
+    /// assert!(hdl_a.is_attached());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn split(
+        self,
+    ) -> (
+        ReadHandle<'list, 'node, R, T>,
+        WriteHandle<'list, 'node, R, T>,
+    ) {
+        // SAFETY: The halves never give up the node, and are only joined back
+        // into a single handle once both are consumed.
+        let read = unsafe { self.alias() };
+        (ReadHandle { hdl: read }, WriteHandle { hdl: self })
+    }
+}
+
+// ---- impl ReadHandle ----
+
+impl<R: ScopedRawMutex, T> ReadHandle<'_, '_, R, T> {
+    /// Access the item immutably within a closure.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    pub fn with_lock<U, F: FnOnce(&T) -> U>(&self, f: F) -> U {
+        self.hdl.with_lock(f)
+    }
+
+    /// Is the node still attached to a list? See [`NodeHandle::is_attached()`].
+    pub fn is_attached(&self) -> bool {
+        self.hdl.is_attached()
+    }
+
+    /// The identity of the node. See [`NodeHandle::id()`].
+    pub fn id(&self) -> NodeId {
+        self.hdl.id()
+    }
+}
+
+// ---- impl WriteHandle ----
+
+impl<'list, 'node, R: ScopedRawMutex, T> WriteHandle<'list, 'node, R, T> {
+    /// Access the item immutably within a closure.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    pub fn with_lock<U, F: FnOnce(&T) -> U>(&self, f: F) -> U {
+        self.hdl.with_lock(f)
+    }
+
+    /// Access the item via a pinned mut reference within a closure.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    pub fn with_lock_pin_mut<U, F: FnOnce(Pin<&mut T>) -> U>(&self, f: F) -> U {
+        self.hdl.with_lock_pin_mut(f)
+    }
+
+    /// Is the node still attached to a list? See [`NodeHandle::is_attached()`].
+    pub fn is_attached(&self) -> bool {
+        self.hdl.is_attached()
+    }
+
+    /// The identity of the node. See [`NodeHandle::id()`].
+    pub fn id(&self) -> NodeId {
+        self.hdl.id()
+    }
+
+    /// Join this handle with its [`ReadHandle`], returning the original
+    /// [`NodeHandle`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `read` is the read half of a different node.
+    pub fn unsplit(self, read: ReadHandle<'list, 'node, R, T>) -> NodeHandle<'list, 'node, R, T> {
+        assert_eq!(
+            self.hdl.hdr(),
+            read.hdl.hdr(),
+            "`unsplit()` called with the halves of two different nodes"
+        );
+        self.hdl
+    }
+}
+
+impl<R: ScopedRawMutex, T: Unpin> WriteHandle<'_, '_, R, T> {
+    /// Access the item via a mut reference within a closure.
+    ///
+    /// The item must implement `T: Unpin`. Consider using
+    /// [`WriteHandle::with_lock_pin_mut()`] if your item does not implement
+    /// `Unpin`.
+    ///
+    /// The mutex is locked for the duration of the closure.
+    pub fn with_lock_mut<U, F: FnOnce(&mut T) -> U>(&self, f: F) -> U {
+        self.hdl.with_lock_mut(f)
+    }
+}
+
+// SAFETY: All access to the item of the node is mediated by the mutex of the
+// list, as for PinList, and the rest of the handle is either immutable while
+// it exists, or only accessed atomically. The halves hold references to the
+// mutex, which may be used from another thread, so it must be Sync. If the
+// item is Send and the mutex is Sync, it is safe to implement Send for
+// ReadHandle.
+unsafe impl<R: ScopedRawMutex + Sync, T: Send> Send for ReadHandle<'_, '_, R, T> {}
+
+// SAFETY: See above. If the item is Send and the mutex is Sync, it is safe to
+// implement Sync for ReadHandle.
+unsafe impl<R: ScopedRawMutex + Sync, T: Send> Sync for ReadHandle<'_, '_, R, T> {}
+
+// SAFETY: See above. If the item is Send and the mutex is Sync, it is safe to
+// implement Send for WriteHandle.
+unsafe impl<R: ScopedRawMutex + Sync, T: Send> Send for WriteHandle<'_, '_, R, T> {}
+
+// SAFETY: See above. If the item is Send and the mutex is Sync, it is safe to
+// implement Sync for WriteHandle.
+unsafe impl<R: ScopedRawMutex + Sync, T: Send> Sync for WriteHandle<'_, '_, R, T> {}
@@ -0,0 +1,144 @@
+//! Static storage for the nodes of a PinList
+
+use core::{mem::MaybeUninit, pin::Pin};
+
+use mutex::ScopedRawMutex;
+use static_cell::StaticCell;
+
+use super::{list::PinList, node::Node};
+
+/// Static storage for a single [`Node`] of a `'static` [`PinList`]
+///
+/// The node can be initialized once, returning a pinned `'static` reference
+/// that can be attached for the remainder of the program.
+///
+/// Requires the `static_cell` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use pinlist::{static_node, blocking::{PinList, StaticNode}};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<CsRm, u64> = PinList::new();
+///
+/// // Either with explicit storage...
+/// static NODE_A: StaticNode<CsRm, u64> = StaticNode::new();
+/// let hdl_a = NODE_A.init(&LIST, 123).attach();
+///
+/// // ...or with the macro
+/// let hdl_b = static_node!(CsRm, u64; &LIST, 456).attach();
+///
+/// let items = LIST.with_iter(|n| n.copied().collect::<Vec<_>>());
+/// assert_eq!(&[123, 456], items.as_slice());
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+pub struct StaticNode<R: ScopedRawMutex + 'static, T: 'static> {
+    cell: StaticCell<Node<'static, R, T>>,
+}
+
+impl<R: ScopedRawMutex + 'static, T: 'static> StaticNode<R, T> {
+    /// Create new, uninitialized, storage for a node.
+    pub const fn new() -> Self {
+        Self {
+            cell: StaticCell::new(),
+        }
+    }
+
+    /// Initialize the node for the given list, returning it pinned.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if this storage has already been initialized.
+    pub fn init(
+        &'static self,
+        list: &'static PinList<R, T>,
+        t: T,
+    ) -> Pin<&'static mut Node<'static, R, T>> {
+        Pin::static_mut(self.cell.init(Node::new_for(list, t)))
+    }
+
+    /// Initialize the node for the given list in place, returning it pinned.
+    ///
+    /// Unlike [`StaticNode::init()`], the item is never passed by value, so
+    /// large items do not need to be built on the stack first. `init` is
+    /// called with the uninitialized item in its final location, and must
+    /// return it once initialized, usually with [`MaybeUninit::write()`] or
+    /// by filling in its fields and calling [`MaybeUninit::assume_init_mut()`].
+    ///
+    /// The item's address while it is initialized is its final address, so
+    /// items that refer to themselves, such as DMA descriptors, can be filled
+    /// in with it. The node is not attached until the returned node is, so
+    /// the item is never visible to iterators before it is initialized.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if this storage has already been initialized, or if `init`
+    /// returns a reference to anything other than the item it was given.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use pinlist::blocking::{PinList, StaticNode};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, [u8; 2048]> = PinList::new();
+    /// static NODE: StaticNode<CsRm, [u8; 2048]> = StaticNode::new();
+    ///
+    /// let node = NODE.init_with(&LIST, |buf| {
+    ///     // SAFETY: any bytes are valid, and this fills all of them
+    ///     unsafe {
+    ///         buf.as_mut_ptr().write_bytes(0xA5, 1);
+    ///         buf.assume_init_mut()
+    ///     }
+    /// });
+    /// let _hdl = node.attach();
+    ///
+    /// assert!(LIST.with_iter(|mut n| n.all(|buf| buf.iter().all(|b| *b == 0xA5))));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn init_with<F>(
+        &'static self,
+        list: &'static PinList<R, T>,
+        init: F,
+    ) -> Pin<&'static mut Node<'static, R, T>>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        Pin::static_mut(Node::init_in_place(self.cell.uninit(), list, init))
+    }
+}
+
+impl<R: ScopedRawMutex + 'static, T: 'static> Default for StaticNode<R, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create a [`Node`] in static storage, returning it pinned.
+///
+/// Takes the mutex and item types, followed by the `'static` list and the
+/// value of the node. Each invocation may only be evaluated once, and panics
+/// if evaluated again.
+///
+/// Requires the `static_cell` feature. See [`StaticNode`] for an example.
+///
+/// [`Node`]: crate::blocking::Node
+/// [`StaticNode`]: crate::blocking::StaticNode
+#[macro_export]
+macro_rules! static_node {
+    ($r:ty, $t:ty; $list:expr, $val:expr $(,)?) => {{
+        static NODE: $crate::blocking::StaticNode<$r, $t> = $crate::blocking::StaticNode::new();
+        NODE.init($list, $val)
+    }};
+}
@@ -0,0 +1,172 @@
+//! Non-blocking access to a PinList and its nodes
+
+use core::{
+    pin::Pin,
+    ptr::{self, addr_of, addr_of_mut},
+    sync::atomic::Ordering,
+};
+
+use mutex::ScopedRawMutex;
+
+use super::{
+    list::{Iter, IterPinMut, PinList, PinListInner},
+    node::NodeHandle,
+};
+
+/// The mutex was already locked, so the operation was not performed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WouldBlock;
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Call the given closure with an [`Iter`] which iterates over `&T`s,
+    /// if the blocking mutex can be locked without waiting.
+    ///
+    /// Returns [`WouldBlock`] immediately, without calling `f()`, if the mutex
+    /// is already locked. Otherwise this behaves like [`PinList::with_iter()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node, WouldBlock};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let _hdl_a = node_a.attach();
+    ///
+    /// assert_eq!(Ok(1), LIST.try_with_iter(|n| n.count()));
+    ///
+    /// // While the list is locked
+    /// LIST.with_iter(|_| {
+    ///     assert_eq!(Err(WouldBlock), LIST.try_with_iter(|n| n.count()));
+    /// });
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn try_with_iter<U, F>(&self, f: F) -> Result<U, WouldBlock>
+    where
+        F: for<'a> FnOnce(Iter<'a, T>) -> U,
+    {
+        self.inner
+            .try_with_lock(|inner| {
+                f(Iter {
+                    iter: inner.list.iter(),
+                })
+            })
+            .ok_or(WouldBlock)
+    }
+
+    /// Call the given closure with an [`IterPinMut`] which iterates over
+    /// `Pin<&mut T>`s, if the blocking mutex can be locked without waiting.
+    ///
+    /// Returns [`WouldBlock`] immediately, without calling `f()`, if the mutex
+    /// is already locked. Otherwise this behaves like
+    /// [`PinList::with_iter_pin_mut()`].
+    pub fn try_with_iter_pin_mut<U, F>(&self, f: F) -> Result<U, WouldBlock>
+    where
+        F: for<'a> FnOnce(IterPinMut<'a, T>) -> U,
+    {
+        self.inner
+            .try_with_lock(|inner| {
+                f(IterPinMut {
+                    iter: inner.list.iter_raw(),
+                })
+            })
+            .ok_or(WouldBlock)
+    }
+}
+
+// ---- impl NodeHandle ----
+
+impl<R: ScopedRawMutex, T> NodeHandle<'_, '_, R, T> {
+    /// Call `f` with the mutex of the list this node is linked into locked,
+    /// or the mutex of the list it was created for, if it is not linked, if
+    /// that mutex can be locked without waiting.
+    fn try_with_inner<U, F>(&self, f: F) -> Result<U, WouldBlock>
+    where
+        F: FnOnce(&mut PinListInner<T>) -> U,
+    {
+        // SAFETY: The handle borrows the node, so it is live, and `linked_to` is
+        // only accessed atomically
+        let linked_to = unsafe { &*addr_of!((*self.hdr().as_ptr()).linked_to) };
+        let mut f = f;
+        loop {
+            let ptr = linked_to.load(Ordering::Acquire);
+            let list: &PinList<R, T> = if ptr.is_null() {
+                self.list()
+            } else {
+                // SAFETY: Nodes are only ever linked into lists that outlive them,
+                // with the same mutex type as the list they were created for.
+                unsafe { &*ptr.cast() }
+            };
+            let res = list.inner.try_with_lock(|inner| {
+                // The pointer is only changed with the lock of the list it points
+                // to held, so if it still matches, it will not change.
+                if ptr::eq(linked_to.load(Ordering::Acquire), ptr) {
+                    Ok(f(inner))
+                } else {
+                    Err(f)
+                }
+            });
+            match res {
+                None => return Err(WouldBlock),
+                Some(Ok(u)) => return Ok(u),
+                Some(Err(g)) => f = g,
+            }
+        }
+    }
+
+    /// Access the item immutably within a closure, if the mutex can be locked
+    /// without waiting.
+    ///
+    /// Returns [`WouldBlock`] immediately, without calling `f()`, if the mutex
+    /// is already locked. Otherwise this behaves like
+    /// [`NodeHandle::with_lock()`].
+    pub fn try_with_lock<U, F: FnOnce(&T) -> U>(&self, f: F) -> Result<U, WouldBlock> {
+        let hdr = self.hdr();
+        self.try_with_inner(|_inner| {
+            // SAFETY: We hold the lock, and we are providing a &T reference, preventing
+            // the item from being moved out
+            f(unsafe { &*addr_of!((*hdr.as_ptr()).t) })
+        })
+    }
+
+    /// Access the item via a pinned mut reference within a closure, if the
+    /// mutex can be locked without waiting.
+    ///
+    /// Returns [`WouldBlock`] immediately, without calling `f()`, if the mutex
+    /// is already locked. Otherwise this behaves like
+    /// [`NodeHandle::with_lock_pin_mut()`].
+    pub fn try_with_lock_pin_mut<U, F: FnOnce(Pin<&mut T>) -> U>(
+        &self,
+        f: F,
+    ) -> Result<U, WouldBlock> {
+        let hdr = self.hdr();
+        self.try_with_inner(|_inner| {
+            // SAFETY: We hold the lock, and we are providing a Pin<&mut T> reference,
+            // preventing the item from being moved out
+            f(unsafe { Pin::new_unchecked(&mut *addr_of_mut!((*hdr.as_ptr()).t)) })
+        })
+    }
+}
+
+impl<R: ScopedRawMutex, T: Unpin> NodeHandle<'_, '_, R, T> {
+    /// Access the item via a mut reference within a closure, if the mutex can
+    /// be locked without waiting.
+    ///
+    /// Returns [`WouldBlock`] immediately, without calling `f()`, if the mutex
+    /// is already locked. Otherwise this behaves like
+    /// [`NodeHandle::with_lock_mut()`].
+    pub fn try_with_lock_mut<U, F: FnOnce(&mut T) -> U>(&self, f: F) -> Result<U, WouldBlock> {
+        self.try_with_lock_pin_mut(|t| f(Pin::into_inner(t)))
+    }
+}
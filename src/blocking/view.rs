@@ -0,0 +1,306 @@
+//! Read-only views of a PinList
+
+use core::marker::PhantomData;
+
+use mutex::ScopedRawMutex;
+
+use super::list::{Iter, PinList};
+
+/// A read-only view of a [`PinList`], which yields a `&U` projected from each node
+///
+/// Obtained by calling [`PinList::map_view()`].
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{PinList, Node};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// struct Client {
+///     name: &'static str,
+///     secret: u64,
+/// }
+///
+/// static LIST: PinList<CsRm, Client> = PinList::new();
+///
+/// let node_a = pin!(Node::new_for(&LIST, Client { name: "a", secret: 123 }));
+/// let _hdl_a = node_a.attach();
+///
+/// // Consumers of the view only see the names
+/// let view = LIST.map_view(|c| &c.name);
+/// view.with_iter(|mut i| assert_eq!("a", *i.next().unwrap()));
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+pub struct MapView<'list, R: ScopedRawMutex, T, U: ?Sized, F> {
+    list: &'list PinList<R, T>,
+    f: F,
+    _u: PhantomData<fn(&T) -> &U>,
+}
+
+/// A read-only view of a [`PinList`], which yields each node as a `&D`
+///
+/// Obtained by calling [`PinList::as_dyn_view()`].
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use core::{fmt::Debug, pin::pin};
+/// use pinlist::blocking::{PinList, Node};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<CsRm, u64> = PinList::new();
+///
+/// let node_a = pin!(Node::new_for(&LIST, 123));
+/// let _hdl_a = node_a.attach();
+///
+/// // Consumers of the view only see `&dyn Debug`
+/// let view = LIST.as_dyn_view::<dyn Debug>(|t| t);
+/// assert_eq!(1, view.len());
+/// view.with_iter(|mut i| assert_eq!("123", format!("{:?}", i.next().unwrap())));
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+pub type DynView<'list, R, T, D> = MapView<'list, R, T, D, fn(&T) -> &D>;
+
+/// An [`Iterator`] over `&U` projections of the nodes of a [`MapView`]
+///
+/// Obtained by calling [`MapView::with_iter()`].
+pub struct MapIter<'a, T, U: ?Sized, F> {
+    iter: Iter<'a, T>,
+    f: &'a F,
+    _u: PhantomData<fn(&T) -> &U>,
+}
+
+/// An [`Iterator`] over `&D` nodes of a [`DynView`]
+///
+/// Obtained by calling [`DynView::with_iter()`](MapView::with_iter).
+pub type DynIter<'a, T, D> = MapIter<'a, T, D, fn(&T) -> &D>;
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Create a read-only [`MapView`] of this list, which yields a `&U`
+    /// projected from each node by `f`.
+    ///
+    /// This is useful for exposing only part of each node to consumers.
+    pub fn map_view<U, F>(&self, f: F) -> MapView<'_, R, T, U, F>
+    where
+        U: ?Sized,
+        F: Fn(&T) -> &U,
+    {
+        MapView {
+            list: self,
+            f,
+            _u: PhantomData,
+        }
+    }
+
+    /// Create a read-only [`DynView`] of this list, which yields each node as a `&D`.
+    ///
+    /// `cast` is used to convert each `&T` into a `&D`, and is typically just
+    /// `|t| t`, relying on unsized coercion to a trait object.
+    ///
+    /// The cast cannot be left out: converting a `&T` to a `&D` generically
+    /// requires a `T: Unsize<D>` bound, and `Unsize` is unstable, so on stable
+    /// Rust the coercion has to happen in a closure where `T` is concrete.
+    ///
+    /// A trait object can only name one trait, plus auto traits such as
+    /// `Send`. To require several traits, define a trait with all of them as
+    /// supertraits, and implement it for every type that implements them.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::{fmt::{Debug, Display}, pin::pin};
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// trait Printable: Debug + Display {}
+    /// impl<T: Debug + Display> Printable for T {}
+    ///
+    /// static LIST: PinList<CsRm, &'static str> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, "a"));
+    /// let _hdl_a = node_a.attach();
+    ///
+    /// let view = LIST.as_dyn_view::<dyn Printable + Send>(|t| t);
+    /// let (display, debug) = view.with_iter(|mut i| {
+    ///     let t = i.next().unwrap();
+    ///     (format!("{t}"), format!("{t:?}"))
+    /// });
+    /// assert_eq!(("a", "\"a\""), (display.as_str(), debug.as_str()));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn as_dyn_view<D: ?Sized>(&self, cast: fn(&T) -> &D) -> DynView<'_, R, T, D> {
+        self.map_view(cast)
+    }
+}
+
+// ---- impl MapView ----
+
+impl<R, T, U, F> MapView<'_, R, T, U, F>
+where
+    R: ScopedRawMutex,
+    U: ?Sized,
+    F: Fn(&T) -> &U,
+{
+    /// Call the given closure with a [`MapIter`] which iterates over `&U`s
+    ///
+    /// The blocking mutex is locked for the duration of the call to `f()`.
+    pub fn with_iter<V, G>(&self, g: G) -> V
+    where
+        G: for<'a> FnOnce(MapIter<'a, T, U, F>) -> V,
+    {
+        self.list.with_iter(|iter| {
+            g(MapIter {
+                iter,
+                f: &self.f,
+                _u: PhantomData,
+            })
+        })
+    }
+
+    /// Find the first `&U` matching `pred`, and call `f` with it.
+    /// See [`PinList::with_find()`].
+    ///
+    /// The blocking mutex is locked for the duration of the search and the
+    /// call to `f()`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// trait Sensor {
+    ///     fn name(&self) -> &'static str;
+    ///     fn read(&self) -> u32;
+    /// }
+    ///
+    /// struct Fixed(&'static str, u32);
+    ///
+    /// impl Sensor for Fixed {
+    ///     fn name(&self) -> &'static str { self.0 }
+    ///     fn read(&self) -> u32 { self.1 }
+    /// }
+    ///
+    /// static LIST: PinList<CsRm, Fixed> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, Fixed("temp", 21)));
+    /// let node_b = pin!(Node::new_for(&LIST, Fixed("load", 7)));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// let view = LIST.as_dyn_view::<dyn Sensor>(|t| t);
+    /// assert_eq!(Some(7), view.with_find(|s| s.name() == "load", |s| s.read()));
+    /// assert_eq!(None, view.with_find(|s| s.name() == "fan", |s| s.read()));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_find<V, P, G>(&self, mut pred: P, g: G) -> Option<V>
+    where
+        P: FnMut(&U) -> bool,
+        G: FnOnce(&U) -> V,
+    {
+        self.list
+            .with_find(|t| pred((self.f)(t)), |t| g((self.f)(t)))
+    }
+
+    /// Fold every `&U` into an accumulator, in order. See [`PinList::with_fold()`].
+    ///
+    /// The blocking mutex is locked for the duration of the fold.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// trait Transfer {
+    ///     fn outstanding(&self) -> usize;
+    /// }
+    ///
+    /// impl Transfer for usize {
+    ///     fn outstanding(&self) -> usize { *self }
+    /// }
+    ///
+    /// static LIST: PinList<CsRm, usize> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 64));
+    /// let node_b = pin!(Node::new_for(&LIST, 128));
+    /// let _hdl_a = node_a.attach();
+    /// let _hdl_b = node_b.attach();
+    ///
+    /// let view = LIST.as_dyn_view::<dyn Transfer>(|t| t);
+    /// assert_eq!(192, view.with_fold(0, |acc, t| acc + t.outstanding()));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn with_fold<B, G>(&self, init: B, mut g: G) -> B
+    where
+        G: FnMut(B, &U) -> B,
+    {
+        self.list.with_fold(init, |acc, t| g(acc, (self.f)(t)))
+    }
+
+    /// The number of nodes attached to the list. See [`PinList::len()`].
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Are there no nodes attached to the list? See [`PinList::is_empty()`].
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
+
+impl<R: ScopedRawMutex, T, U: ?Sized, F: Clone> Clone for MapView<'_, R, T, U, F> {
+    fn clone(&self) -> Self {
+        Self {
+            list: self.list,
+            f: self.f.clone(),
+            _u: PhantomData,
+        }
+    }
+}
+
+impl<R: ScopedRawMutex, T, U: ?Sized, F: Copy> Copy for MapView<'_, R, T, U, F> {}
+
+// ---- impl MapIter ----
+
+impl<'a, T, U, F> Iterator for MapIter<'a, T, U, F>
+where
+    U: ?Sized + 'a,
+    F: Fn(&T) -> &U,
+{
+    type Item = &'a U;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(self.f)
+    }
+}
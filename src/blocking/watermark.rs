@@ -0,0 +1,116 @@
+//! Watermark thresholds on the number of nodes in a PinList
+
+use mutex::ScopedRawMutex;
+
+use super::list::{PinList, PinListInner};
+
+/// Which watermark of a [`PinList`] was crossed
+///
+/// See [`PinList::set_watermarks()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Watermark {
+    /// The number of nodes rose above the high watermark
+    High,
+    /// The number of nodes fell to or below the low watermark, after having
+    /// been above the high watermark
+    Low,
+}
+
+/// The watermarks registered on a list, and which side of them it is on
+pub(crate) struct Watermarks {
+    low: usize,
+    high: usize,
+    on_cross: fn(Watermark, usize),
+    above: bool,
+}
+
+// ---- impl PinListInner ----
+
+impl<T> PinListInner<T> {
+    /// Fire the watermark callback, if the number of nodes crossed a watermark.
+    ///
+    /// Must be called after every change to the number of nodes.
+    pub(crate) fn check_watermarks(&mut self) {
+        let Some(wm) = self.watermarks.as_mut() else {
+            return;
+        };
+        let len = self.list.len();
+        if !wm.above && len > wm.high {
+            wm.above = true;
+            (wm.on_cross)(Watermark::High, len);
+        } else if wm.above && len <= wm.low {
+            wm.above = false;
+            (wm.on_cross)(Watermark::Low, len);
+        }
+    }
+}
+
+// ---- impl PinList ----
+
+impl<R: ScopedRawMutex, T> PinList<R, T> {
+    /// Register low and high watermarks on the number of attached nodes.
+    ///
+    /// `on_cross` is called with [`Watermark::High`] and the new number of
+    /// nodes when the number rises above `high`, and then with
+    /// [`Watermark::Low`] once it falls to `low` or below. Each is only called
+    /// on the transition, not on every change, so `low` and `high` act as
+    /// hysteresis. Setting `low` to zero fires once the list is empty.
+    ///
+    /// If the list is already above `high`, `on_cross` is not called until it
+    /// next falls to `low`. Any previously registered watermarks are replaced.
+    ///
+    /// `on_cross` is called with the blocking mutex locked, so it must not
+    /// access this list.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::{pin::pin, sync::atomic::{AtomicBool, Ordering}};
+    /// use pinlist::blocking::{PinList, Node, Watermark};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static PENDING: PinList<CsRm, u64> = PinList::new();
+    /// static PAUSED: AtomicBool = AtomicBool::new(false);
+    ///
+    /// PENDING.set_watermarks(0, 1, |wm, _len| {
+    ///     PAUSED.store(wm == Watermark::High, Ordering::Relaxed);
+    /// });
+    ///
+    /// let node_a = pin!(Node::new_for(&PENDING, 123));
+    /// let _hdl_a = node_a.attach();
+    /// assert!(!PAUSED.load(Ordering::Relaxed));
+    /// {
+    ///     let node_b = pin!(Node::new_for(&PENDING, 456));
+    ///     let _hdl_b = node_b.attach();
+    ///     assert!(PAUSED.load(Ordering::Relaxed));
+    /// }
+    /// // Still above the low watermark
+    /// assert!(PAUSED.load(Ordering::Relaxed));
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn set_watermarks(&self, low: usize, high: usize, on_cross: fn(Watermark, usize)) {
+        debug_assert!(
+            low < high,
+            "the low watermark must be below the high watermark"
+        );
+        self.inner.with_lock(|inner| {
+            inner.watermarks = Some(Watermarks {
+                low,
+                high,
+                on_cross,
+                above: inner.list.len() > high,
+            });
+        })
+    }
+
+    /// Remove any watermarks registered with [`PinList::set_watermarks()`].
+    pub fn clear_watermarks(&self) {
+        self.inner.with_lock(|inner| inner.watermarks = None)
+    }
+}
@@ -0,0 +1,180 @@
+//! Handles to attached nodes that do not borrow the node
+
+use core::pin::Pin;
+
+use mutex::ScopedRawMutex;
+
+use super::{
+    id::NodeId,
+    list::PinList,
+    node::{NodeHandle, NodeHeader},
+};
+
+/// A handle to a node that does not borrow it, and so may outlive it
+///
+/// Each use locks the list, and checks that the node is still attached to
+/// it with the same [`NodeId`], so a node that was dropped, detached, or
+/// attached again since the handle was created is never accessed. This
+/// requires walking the list to find the node, so each use takes time
+/// proportional to its position.
+///
+/// Obtained by calling [`NodeHandle::weak()`].
+///
+/// ## Example
+///
+/// ```rust
+/// # // only works with `_docs` active so we have the CS impl
+/// # #[cfg(feature = "_docs")]
+/// # fn example() {
+/// use core::pin::pin;
+/// use pinlist::blocking::{PinList, Node};
+/// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+///
+/// static LIST: PinList<CsRm, u64> = PinList::new();
+///
+/// let weak = {
+///     let node_a = pin!(Node::new_for(&LIST, 123));
+///     let hdl_a = node_a.attach();
+///     let weak = hdl_a.weak();
+///     assert_eq!(Some(123), weak.with_lock(|t| *t));
+///     weak
+/// };
+/// // The node has been dropped
+/// assert_eq!(None, weak.with_lock(|t| *t));
+/// # }
+/// # #[cfg(feature = "_docs")]
+/// # example()
+/// ```
+pub struct WeakNodeHandle<'list, R: ScopedRawMutex, T> {
+    list: &'list PinList<R, T>,
+    id: NodeId,
+}
+
+// ---- impl NodeHandle ----
+
+impl<'list, R: ScopedRawMutex, T> NodeHandle<'list, '_, R, T> {
+    /// Create a [`WeakNodeHandle`] to this node.
+    ///
+    /// The weak handle only finds the node in the list it was created for.
+    /// If the node is moved to a different list, for example by
+    /// [`PinList::split_off()`], the weak handle treats it as detached, even
+    /// if it is later moved back.
+    pub fn weak(&self) -> WeakNodeHandle<'list, R, T> {
+        WeakNodeHandle {
+            list: self.list(),
+            id: self.id(),
+        }
+    }
+}
+
+// ---- impl WeakNodeHandle ----
+
+impl<R: ScopedRawMutex, T> WeakNodeHandle<'_, R, T> {
+    /// The identity of the node this handle refers to
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Call `f` with the item of the node, if it is still attached.
+    fn with_item<U, F>(&self, f: F) -> Option<U>
+    where
+        F: FnOnce(Pin<&mut T>) -> U,
+    {
+        self.list.inner.with_lock(|inner| {
+            let hdr = inner
+                .list
+                .iter_raw()
+                // SAFETY: We hold the lock, so the node is live
+                .find(|hdr| self.id == NodeId::of(unsafe { hdr.as_ref() }))?;
+            // SAFETY: We hold the lock of the list the node is linked into
+            Some(f(unsafe { NodeHeader::item_pin_mut(hdr) }))
+        })
+    }
+
+    /// Is the node still attached to the list?
+    ///
+    /// The mutex is locked while the list is searched for the node.
+    pub fn is_attached(&self) -> bool {
+        self.with_item(|_| ()).is_some()
+    }
+
+    /// Access the item immutably within a closure, if the node is still
+    /// attached.
+    ///
+    /// The mutex is locked while the list is searched for the node, and for
+    /// the duration of the closure.
+    pub fn with_lock<U, F: FnOnce(&T) -> U>(&self, f: F) -> Option<U> {
+        self.with_item(|t| f(t.into_ref().get_ref()))
+    }
+
+    /// Access the item via a pinned mut reference within a closure, if the
+    /// node is still attached.
+    ///
+    /// The mutex is locked while the list is searched for the node, and for
+    /// the duration of the closure.
+    pub fn with_lock_pin_mut<U, F: FnOnce(Pin<&mut T>) -> U>(&self, f: F) -> Option<U> {
+        self.with_item(f)
+    }
+
+    /// Ask the owner of the node to detach it, if it is still attached.
+    ///
+    /// Returns `false` if the node is no longer attached. See
+    /// [`PinList::request_detach()`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # // only works with `_docs` active so we have the CS impl
+    /// # #[cfg(feature = "_docs")]
+    /// # fn example() {
+    /// use core::pin::pin;
+    /// use pinlist::blocking::{PinList, Node};
+    /// use mutex::raw_impls::cs::CriticalSectionRawMutex as CsRm;
+    ///
+    /// static LIST: PinList<CsRm, u64> = PinList::new();
+    ///
+    /// let node_a = pin!(Node::new_for(&LIST, 123));
+    /// let hdl_a = node_a.attach();
+    /// let weak = hdl_a.weak();
+    ///
+    /// assert!(weak.request_detach());
+    /// assert!(hdl_a.detach_requested());
+    ///
+    /// // The owner complies
+    /// let _node_a = hdl_a.detach();
+    /// assert!(!weak.request_detach());
+    /// # }
+    /// # #[cfg(feature = "_docs")]
+    /// # example()
+    /// ```
+    pub fn request_detach(&self) -> bool {
+        self.list.request_detach(self.id)
+    }
+}
+
+impl<R: ScopedRawMutex, T: Unpin> WeakNodeHandle<'_, R, T> {
+    /// Access the item via a mut reference within a closure, if the node is
+    /// still attached.
+    ///
+    /// The mutex is locked while the list is searched for the node, and for
+    /// the duration of the closure.
+    pub fn with_lock_mut<U, F: FnOnce(&mut T) -> U>(&self, f: F) -> Option<U> {
+        self.with_lock_pin_mut(|t| f(Pin::into_inner(t)))
+    }
+}
+
+impl<R: ScopedRawMutex, T> Clone for WeakNodeHandle<'_, R, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: ScopedRawMutex, T> Copy for WeakNodeHandle<'_, R, T> {}
+
+impl<R: ScopedRawMutex, T> core::fmt::Debug for WeakNodeHandle<'_, R, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WeakNodeHandle")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}